@@ -1,20 +1,569 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-voting
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as Voting;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	sp_runtime::traits::{IdentifyAccount, Saturating, Zero},
+	traits::{Currency, EnsureOrigin, Hooks, StorePreimage},
+	weights::Weight,
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_std::{prelude::*, vec};
+
+const SEED: u32 = 0;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Registers `who` as a voter and gives it enough balance to make as many proposals and cast as
+/// many of the most expensive votes as it can have locked at once.
+fn setup_voter<T: Config>(who: &T::AccountId) {
+	let max_vote_cost = Voting::<T>::vote_cost(&VoteDecision::Aye(T::VoteLimit::get()))
+		.expect("the most expensive vote does not overflow; qed");
+	let rounds: BalanceOf<T> = (T::MaxLockedVotes::get() + 1).into();
+	T::Currency::make_free_balance_be(
+		who,
+		T::Currency::minimum_balance() +
+			(T::ProposalDeposit::get() + max_vote_cost).saturating_mul(rounds),
+	);
+	RegisteredVoters::<T>::insert(who, Registration::<T>::new(None));
+	AmountVoters::<T>::mutate(|amount| *amount = Some(amount.unwrap_or_default() + 1));
+}
+
+/// Fills the registration queue up to one free slot, so the request being benchmarked is the
+/// last one searched.
+fn fill_pending_registrations<T: Config>() {
+	let pending: Vec<_> = (1..T::MaxPendingRegistrations::get())
+		.map(|i| RegistrationRequest::<T>::new(account("pending", i, SEED), Default::default()))
+		.collect();
+	PendingRegistrations::<T>::put(BoundedVec::truncate_from(pending));
+}
+
+/// Block far enough in the future that the removal threshold has not been reached yet.
+fn proposal_end<T: Config>() -> T::BlockNumber {
+	frame_system::Pallet::<T>::block_number() +
+		T::VoteRemovalThreshold::get().saturating_add(100).into()
+}
+
+/// Parameters of an open aye/nay proposal with metadata of the maximum allowed length.
+fn max_params<T: Config>() -> ProposalParams {
+	ProposalParams {
+		title: vec![b'a'; T::MaxTitleLength::get() as usize],
+		summary: vec![b'a'; T::MaxSummaryLength::get() as usize],
+		uri: vec![b'a'; T::MaxUriLength::get() as usize],
+		..Default::default()
+	}
+}
+
+/// A call too big to be inlined, so it has to be fetched from the preimage provider.
+fn noted_call<T: Config>() -> BoundedCallOf<T> {
+	let call: CallOf<T> = frame_system::Call::<T>::remark { remark: vec![0u8; 256] }.into();
+	T::Preimages::bound(call).expect("preimage provider accepts small calls; qed")
+}
+
+/// Creates a proposal ending one block after the previous one, so the end queue of a block never
+/// fills up however many proposals a benchmark creates.
+fn create_proposal<T: Config>(proposer: &T::AccountId) -> ProposalId {
+	let end = proposal_end::<T>() + Voting::<T>::get_proposal_counter().into();
+	create_proposal_ending_at::<T>(proposer, end)
+}
+
+fn create_proposal_ending_at<T: Config>(
+	proposer: &T::AccountId,
+	end: T::BlockNumber,
+) -> ProposalId {
+	Voting::<T>::make_proposal(
+		RawOrigin::Signed(proposer.clone()).into(),
+		T::Hash::default(),
+		max_params::<T>(),
+		end,
+		Some(noted_call::<T>()),
+	)
+	.expect("proposer is registered and end block is in the future; qed");
+	Voting::<T>::get_proposal_counter()
+}
+
+/// Creates a secret ballot whose commit phase lasts 100 blocks.
+fn create_secret_proposal<T: Config>(proposer: &T::AccountId) -> ProposalId {
+	let end_block =
+		frame_system::Pallet::<T>::block_number() + T::RevealPeriod::get() + 100u32.into();
+	Voting::<T>::make_proposal(
+		RawOrigin::Signed(proposer.clone()).into(),
+		T::Hash::default(),
+		ProposalParams { secret: true, ..Default::default() },
+		end_block,
+		None,
+	)
+	.expect("proposer is registered and the commit phase is not over; qed");
+	Voting::<T>::get_proposal_counter()
+}
+
+/// Registers `d` voters delegating all their votes to `delegate`.
+fn add_delegators<T: Config>(delegate: &T::AccountId, d: u32) {
+	for i in 0..d {
+		let delegator: T::AccountId = account("delegator", i, SEED);
+		setup_voter::<T>(&delegator);
+		Voting::<T>::delegate(
+			RawOrigin::Signed(delegator).into(),
+			delegate.clone(),
+			DelegationScope::All,
+		)
+		.expect("delegate is registered and has room for delegators; qed");
+	}
+}
+
+/// Tops up the ayes of a proposal so it reaches the configured quorum and passes.
+fn meet_quorum<T: Config>(proposal_id: ProposalId) {
+	Proposals::<T>::mutate(proposal_id, |proposal| {
+		if let Some(proposal) = proposal {
+			proposal.ayes = proposal.ayes.max(T::QuorumPoints::get()).max(proposal.nays + 1);
+			proposal.voters = proposal.voters.max(AmountVoters::<T>::get().unwrap_or_default());
+		}
+	});
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	register_voter {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		assert!(Voting::<T>::is_registered(&voter));
+		assert_last_event::<T>(Event::VoterRegistered { who: voter }.into());
+	}
+
+	remove_voter {
+		let v in 0 .. T::MaxLockedVotes::get();
+		let d in 0 .. T::MaxDelegators::get();
+		let origin =
+			T::RemovalOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let proposer: T::AccountId = account("proposer", 0, SEED);
+		setup_voter::<T>(&proposer);
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+		add_delegators::<T>(&voter, d);
+		for _ in 0 .. v {
+			let proposal_id = create_proposal::<T>(&proposer);
+			let decision = VoteDecision::Aye(T::VoteLimit::get());
+			Voting::<T>::vote(RawOrigin::Signed(voter.clone()).into(), proposal_id, decision)?;
+		}
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		assert!(!RegisteredVoters::<T>::contains_key(&voter));
+		assert!(LockedVotes::<T>::get(&voter).is_empty());
+		assert_last_event::<T>(Event::VoterRemoved { who: voter }.into());
+	}
+
+	suspend_voter {
+		let origin =
+			T::RemovalOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		assert!(Voting::<T>::is_suspended(&voter));
+		assert_last_event::<T>(Event::VoterSuspended { who: voter }.into());
+	}
+
+	reinstate_voter {
+		let origin =
+			T::RemovalOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+		SuspendedVoters::<T>::insert(&voter, ());
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		assert!(Voting::<T>::is_registered(&voter));
+		assert_last_event::<T>(Event::VoterReinstated { who: voter }.into());
+	}
+
+	renew_registration {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		let expires_at = RegisteredVoters::<T>::get(&voter).and_then(|registration| registration.expires_at);
+		assert_last_event::<T>(Event::RegistrationRenewed { who: voter, expires_at }.into());
+	}
+
+	issue_credits {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+		let credits = T::CreditsPerRound::get();
+	}: _<T::RuntimeOrigin>(origin, voter.clone(), credits)
+	verify {
+		assert_eq!(Voting::<T>::credits_of(&voter).credits, credits);
+	}
+
+	request_registration {
+		fill_pending_registrations::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance() + T::RegistrationDeposit::get());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(PendingRegistrations::<T>::get().len() as u32, T::MaxPendingRegistrations::get());
+		assert_last_event::<T>(
+			Event::RegistrationRequested { who: caller, deposit: T::RegistrationDeposit::get() }.into(),
+		);
+	}
+
+	approve_registration {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		fill_pending_registrations::<T>();
+		let voter: T::AccountId = account("voter", 0, SEED);
+		T::Currency::make_free_balance_be(&voter, T::Currency::minimum_balance() + T::RegistrationDeposit::get());
+		Voting::<T>::request_registration(RawOrigin::Signed(voter.clone()).into())?;
+	}: _<T::RuntimeOrigin>(origin, voter.clone())
+	verify {
+		assert!(Voting::<T>::is_registered(&voter));
+		assert_last_event::<T>(Event::RegistrationApproved { who: voter }.into());
+	}
+
+	reject_registration {
+		let origin =
+			T::RegistrarOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		fill_pending_registrations::<T>();
+		let voter: T::AccountId = account("voter", 0, SEED);
+		T::Currency::make_free_balance_be(&voter, T::Currency::minimum_balance() + T::RegistrationDeposit::get());
+		Voting::<T>::request_registration(RawOrigin::Signed(voter.clone()).into())?;
+	}: _<T::RuntimeOrigin>(origin, voter.clone(), true)
+	verify {
+		assert!(!Voting::<T>::is_registered(&voter));
+		assert_last_event::<T>(Event::RegistrationRejected { who: voter, slashed: true }.into());
+	}
+
+	make_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let end_block = proposal_end::<T>();
+		let params = max_params::<T>();
+		let title = params.title.clone();
+		let call = Some(noted_call::<T>());
+	}: _(RawOrigin::Signed(caller.clone()), T::Hash::default(), params, end_block, call)
+	verify {
+		assert!(Voting::<T>::proposal_exists(1));
+		let title = title.try_into().expect("title has the maximum length; qed");
+		assert_last_event::<T>(Event::ProposalSubmitted { proposal_id: 1, who: caller, title }.into());
+	}
+
+	make_options_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let end_block = proposal_end::<T>();
+		// Worst case: the maximum number of options, each of the maximum length.
+		let params = ProposalParams {
+			options: vec![
+				vec![b'a'; T::MaxOptionLength::get() as usize];
+				T::MaxOptions::get() as usize
+			],
+			..max_params::<T>()
+		};
+	}: make_proposal(RawOrigin::Signed(caller.clone()), T::Hash::default(), params, end_block, None)
+	verify {
+		let proposal = Voting::<T>::get_proposal(&1).expect("proposal was created; qed");
+		assert_eq!(proposal.options.len() as u32, T::MaxOptions::get());
+	}
+
+	increase_proposal_time {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_proposal::<T>(&caller);
+		let end_block = proposal_end::<T>() + 10u32.into();
+	}: _(RawOrigin::Signed(caller), proposal_id, end_block)
+	verify {
+		assert_last_event::<T>(Event::ProposalUpdated { proposal_id, end_block }.into());
+	}
+
+	cancel_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_proposal::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), proposal_id)
+	verify {
+		assert_last_event::<T>(Event::ProposalCanceled { proposal_id }.into());
+	}
+
+	vote {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		// Worst case: every delegator pays for the vote cast on their behalf.
+		add_delegators::<T>(&caller, d);
+		let proposal_id = create_proposal::<T>(&caller);
+		// Worst case: the vote replaces the one the delegate of the caller cast for them.
+		let delegated = VoteDecision::Aye(T::VoteLimit::get());
+		Voting::<T>::do_vote(&caller, proposal_id, &delegated, true)?;
+		let decision = VoteDecision::Nay(T::VoteLimit::get());
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id, decision)
+	verify {
+		let vote = Votes::<T>::get(&caller, proposal_id).expect("vote was cast; qed");
+		assert!(!vote.delegated);
+		for delegator in Delegators::<T>::get(&caller) {
+			assert!(Voting::<T>::vote_casted(&delegator, &proposal_id));
+		}
+	}
+
+	update_vote {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		add_delegators::<T>(&caller, d);
+		let proposal_id = create_proposal::<T>(&caller);
+		let decision = VoteDecision::Aye(1);
+		Voting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), proposal_id, decision)?;
+		// Worst case: switching sides while reserving the largest possible difference.
+		let new = VoteDecision::Nay(T::VoteLimit::get());
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id, new.clone())
+	verify {
+		for delegator in Delegators::<T>::get(&caller) {
+			let vote = Votes::<T>::get(&delegator, proposal_id).expect("vote was cast again; qed");
+			assert_eq!(vote.vote_decision, new);
+		}
+		frame_system::Pallet::<T>::assert_has_event(
+			Event::VoteUpdated { proposal_id, who: caller, previous: VoteDecision::Aye(1), new }
+				.into(),
+		);
+	}
+
+	cancel_vote {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		add_delegators::<T>(&caller, d);
+		let proposal_id = create_proposal::<T>(&caller);
+		let decision = VoteDecision::Aye(T::VoteLimit::get());
+		Voting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), proposal_id, decision)?;
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id)
+	verify {
+		assert!(!Voting::<T>::vote_casted(&caller, &proposal_id));
+		for delegator in Delegators::<T>::get(&caller) {
+			assert!(!Voting::<T>::vote_casted(&delegator, &proposal_id));
+		}
+	}
+
+	finish_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_proposal::<T>(&caller);
+		// Worst case: the proposal passes and its call is fetched and dispatched.
+		Voting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), proposal_id, VoteDecision::Aye(1))?;
+		meet_quorum::<T>(proposal_id);
+		frame_system::Pallet::<T>::set_block_number(proposal_end::<T>() + 1u32.into());
+	}: _(RawOrigin::Signed(caller), proposal_id)
+	verify {
+		assert_last_event::<T>(Event::ProposalEnacted { proposal_id, result: Ok(()) }.into());
+	}
+
+	close_proposals {
+		let p in 0 .. T::MaxProposalsEndingPerBlock::get();
+		// Worst case: every proposal passes and its call is fetched and dispatched.
+		let end = proposal_end::<T>();
+		for i in 0 .. p {
+			let voter: T::AccountId = account("voter", i, SEED);
+			setup_voter::<T>(&voter);
+			let proposal_id = create_proposal_ending_at::<T>(&voter, end);
+			Voting::<T>::vote(RawOrigin::Signed(voter).into(), proposal_id, VoteDecision::Aye(1))?;
+		}
+		for proposal_id in 1 ..= p {
+			meet_quorum::<T>(proposal_id);
+		}
+		frame_system::Pallet::<T>::set_block_number(end);
+	}: {
+		Voting::<T>::on_initialize(end);
+	}
+	verify {
+		assert!(ProposalsEndingAt::<T>::get(end).is_empty());
+		if p > 0 {
+			let proposal = Voting::<T>::get_proposal(&p).expect("proposal was created; qed");
+			assert_eq!(proposal.status, ProposalStatus::Passed);
+		}
+	}
+
+	on_idle {
+		let p in 0 .. T::MaxLockedVotes::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		// Running proposals are checked but stay open, so every pass goes over all of them.
+		for _ in 0 .. p {
+			create_proposal::<T>(&caller);
+		}
+		let now = frame_system::Pallet::<T>::block_number();
+	}: {
+		Voting::<T>::on_idle(now, Weight::MAX);
+	}
+	verify {
+		assert_eq!(NextSweep::<T>::get(), SweepCursor::Proposals(1));
+	}
+
+	prune_registration {
+		let d in 0 .. T::MaxDelegators::get();
+		let voter: T::AccountId = account("voter", 0, SEED);
+		setup_voter::<T>(&voter);
+		// Worst case: the voter is suspended, has voice credits, delegates and has delegators.
+		let delegate: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&delegate);
+		Voting::<T>::delegate(RawOrigin::Signed(voter.clone()).into(), delegate, DelegationScope::All)?;
+		add_delegators::<T>(&voter, d);
+		SuspendedVoters::<T>::insert(&voter, ());
+		VoiceCredits::<T>::insert(&voter, CreditBalance { round: 0, credits: 1 });
+	}: {
+		Voting::<T>::remove_expired_registration(&voter);
+	}
+	verify {
+		assert!(!RegisteredVoters::<T>::contains_key(&voter));
+		assert!(Delegators::<T>::get(&voter).is_empty());
+		assert_last_event::<T>(Event::RegistrationExpired { who: voter }.into());
+	}
+
+	prune_eligible_voter {
+		let voter: T::AccountId = account("voter", 0, SEED);
+		EligibleVoters::<T>::insert(&voter, T::BlockNumber::zero());
+		AmountEligibleVoters::<T>::put(1);
+		let now = frame_system::Pallet::<T>::block_number();
+	}: {
+		// The account has no eligibility, so every check is made before it is removed.
+		if Voting::<T>::eligible_voter_lapsed(&voter, T::BlockNumber::zero(), now) {
+			Voting::<T>::remove_eligible_voter(&voter);
+		}
+	}
+	verify {
+		assert!(!EligibleVoters::<T>::contains_key(&voter));
+		assert_eq!(AmountEligibleVoters::<T>::get(), 0);
+	}
+
+	unlock_balance {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_proposal::<T>(&caller);
+		let decision = VoteDecision::Aye(T::VoteLimit::get());
+		Voting::<T>::vote(RawOrigin::Signed(caller.clone()).into(), proposal_id, decision)?;
+		frame_system::Pallet::<T>::set_block_number(proposal_end::<T>() + 1u32.into());
+		Voting::<T>::finish_proposal(RawOrigin::Signed(caller.clone()).into(), proposal_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id)
+	verify {
+		assert_last_event::<T>(Event::BalanceUnlocked { proposal_id, who: caller }.into());
+	}
+
+	delegate {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		// Worst case: the caller replaces a delegation, and the delegate is the start of the
+		// longest chain of delegations allowed and has room for a single delegator.
+		let previous: T::AccountId = account("previous", 0, SEED);
+		setup_voter::<T>(&previous);
+		Voting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			previous,
+			DelegationScope::All,
+		)?;
+		let chain: Vec<T::AccountId> =
+			(0 .. T::MaxDelegationDepth::get()).map(|i| account("chain", i, SEED)).collect();
+		for account in chain.iter() {
+			setup_voter::<T>(account);
+		}
+		for pair in chain.windows(2) {
+			Voting::<T>::delegate(
+				RawOrigin::Signed(pair[0].clone()).into(),
+				pair[1].clone(),
+				DelegationScope::All,
+			)?;
+		}
+		let to = chain[0].clone();
+		add_delegators::<T>(&to, T::MaxDelegators::get() - 1);
+	}: _(RawOrigin::Signed(caller.clone()), to.clone(), DelegationScope::All)
+	verify {
+		assert_last_event::<T>(Event::Delegated { who: caller, to, scope: DelegationScope::All }.into());
+	}
+
+	undelegate {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		setup_voter::<T>(&delegate);
+		add_delegators::<T>(&delegate, T::MaxDelegators::get() - 1);
+		Voting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			delegate,
+			DelegationScope::All,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Voting::<T>::delegation_of(&caller).is_none());
+		assert_last_event::<T>(Event::Undelegated { who: caller }.into());
+	}
+
+	submit_signed_votes {
+		let n in 1 .. T::MaxSignedVotes::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_proposal::<T>(&caller);
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		let votes = (0 .. n)
+			.map(|i| {
+				let signer = T::BenchmarkHelper::signer(i);
+				let vote_decision = VoteDecision::Aye(T::VoteLimit::get());
+				let payload = SignedVotePayload {
+					genesis_hash,
+					proposal_id,
+					vote_decision: vote_decision.clone(),
+					nonce: 0,
+				};
+				let signature = T::BenchmarkHelper::sign(&signer, &payload.encode());
+				let who = signer.into_account();
+				setup_voter::<T>(&who);
+				(who, vote_decision, 0, signature)
+			})
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), proposal_id, votes)
+	verify {
+		assert_last_event::<T>(
+			Event::SignedVotesSubmitted { proposal_id, cast: n, rejected: 0 }.into(),
+		);
+	}
+
+	commit_vote {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal_id = create_secret_proposal::<T>(&caller);
+		let decision = VoteDecision::Aye(T::VoteLimit::get());
+		let commitment = Voting::<T>::vote_commitment(&caller, proposal_id, &decision, &[0; 32]);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id, commitment)
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller, proposal_id));
+		assert_last_event::<T>(Event::VoteCommitted { proposal_id, who: caller }.into());
+	}
+
+	reveal_vote {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		setup_voter::<T>(&caller);
+		let proposal_id = create_secret_proposal::<T>(&caller);
+		// Worst case: the smallest vote, giving back most of the committed cost.
+		let decision = VoteDecision::Nay(1);
+		let commitment = Voting::<T>::vote_commitment(&caller, proposal_id, &decision, &[0; 32]);
+		Voting::<T>::commit_vote(RawOrigin::Signed(caller.clone()).into(), proposal_id, commitment)?;
+		let reveal_start = Voting::<T>::get_proposal(&proposal_id)
+			.expect("proposal was just created; qed")
+			.reveal_start();
+		frame_system::Pallet::<T>::set_block_number(reveal_start);
+	}: _(RawOrigin::Signed(caller.clone()), proposal_id, decision.clone(), [0; 32])
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(Voting::<T>::vote_casted(&caller, &proposal_id));
+		assert_last_event::<T>(
+			Event::VoteRevealed { proposal_id, who: caller, vote_decision: decision }.into(),
+		);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(Voting, crate::mock::new_test_ext(), crate::mock::Test);
 }
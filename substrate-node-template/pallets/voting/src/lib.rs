@@ -11,8 +11,21 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod weights;
+pub use weights::*;
+
 mod types;
-pub use types::{Proposal, ProposalStatus, Vote, VoteDecision};
+pub use types::{
+	ApprovalThreshold, CollateralMode, Commitment, CreditBalance, Delegation, DelegationScope,
+	Proposal, ProposalParams, ProposalStatus, Registration, RegistrationRequest, SignedVotePayload,
+	SweepCursor, Vote, VoteDecision,
+};
+
+mod traits;
+#[cfg(feature = "runtime-benchmarks")]
+pub use traits::BenchmarkHelper;
+pub use traits::{Linear, Quadratic, ScaledQuadratic, VoteCost, VoterEligibility};
 
 pub type ProposalId = u32;
 
@@ -21,20 +34,66 @@ pub mod pallet {
 	use core::cmp::Ordering;
 
 	use frame_support::{
+		dispatch::{extract_actual_weight, GetDispatchInfo, PostDispatchInfo},
 		ensure,
 		pallet_prelude::*,
-		traits::{Currency, LockableCurrency, ReservableCurrency},
+		sp_runtime::{
+			traits::{
+				CheckedMul, Dispatchable, Hash as HashT, IdentifyAccount, SaturatedConversion,
+				Saturating, Verify, Zero,
+			},
+			Perbill,
+		},
+		storage::with_storage_layer,
+		traits::{
+			Bounded, Contains, Currency, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+			OnUnbalanced, QueryPreimage, StorePreimage, WithdrawReasons,
+		},
 		Blake2_128Concat,
 	};
 	use frame_system::pallet_prelude::{OriginFor, *};
+	use sp_std::prelude::*;
 
-	use crate::{Proposal, ProposalId, ProposalStatus, Vote, VoteDecision};
+	use crate::{
+		ApprovalThreshold, CollateralMode, Commitment, CreditBalance, Delegation, DelegationScope,
+		Proposal, ProposalId, ProposalParams, ProposalStatus, Registration, RegistrationRequest,
+		SignedVotePayload, SweepCursor, Vote, VoteCost, VoteDecision, VoterEligibility, WeightInfo,
+	};
 
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+	pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+		<T as frame_system::Config>::AccountId,
+	>>::ReserveIdentifier;
+	pub type CallOf<T> = <T as Config>::RuntimeCall;
+	pub type BoundedCallOf<T> = Bounded<CallOf<T>>;
+	pub type VoteOf<T> = Vote<<T as Config>::MaxOptions>;
+	pub type VoteDecisionOf<T> = VoteDecision<<T as Config>::MaxOptions>;
+	pub type CommitmentOf<T> = Commitment<BalanceOf<T>, <T as frame_system::Config>::Hash>;
+	pub type SignedVoteOf<T> = (
+		<T as frame_system::Config>::AccountId,
+		VoteDecisionOf<T>,
+		u32,
+		<T as Config>::OffchainSignature,
+	);
+	pub type OptionsOf<T> =
+		BoundedVec<BoundedVec<u8, <T as Config>::MaxOptionLength>, <T as Config>::MaxOptions>;
+	pub type ProposalParamsOf<T> = ProposalParams<
+		BoundedVec<u8, <T as Config>::MaxTitleLength>,
+		BoundedVec<u8, <T as Config>::MaxSummaryLength>,
+		BoundedVec<u8, <T as Config>::MaxUriLength>,
+		OptionsOf<T>,
+	>;
+
+	///Current storage version of the pallet.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -44,28 +103,231 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// Type to access the Balances Pallet.
 		type Currency: Currency<Self::AccountId>
-			+ ReservableCurrency<Self::AccountId>
+			+ NamedReservableCurrency<Self::AccountId>
 			+ LockableCurrency<Self::AccountId>;
 
+		///Identifier of the named reserve holding every deposit and vote cost taken by this
+		/// pallet, so they cannot be released by another pallet.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		///Identifier of the lock backing votes when `CollateralMode` is `Lock`.
+		#[pallet::constant]
+		type LockId: Get<LockIdentifier>;
+
+		///Origin allowed to register new voters. Use `EnsureRoot` to keep registration behind
+		/// root.
+		type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		///Origin allowed to remove, suspend and reinstate registered voters.
+		type RemovalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		///Deposit reserved from an account requesting to be registered as a voter. It is returned
+		/// when the request is approved, and returned or slashed when it is rejected.
+		#[pallet::constant]
+		type RegistrationDeposit: Get<BalanceOf<Self>>;
+
+		///The limit of registration requests waiting for the registrar.
+		#[pallet::constant]
+		type MaxPendingRegistrations: Get<u32>;
+
+		///Deposit reserved from the proposer when a proposal is submitted. It is returned when
+		/// the proposal finishes, unless it is slashed by the rules below.
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+
+		///Whether the deposit of a proposal is slashed when the proposer cancels it.
+		#[pallet::constant]
+		type SlashCanceledProposals: Get<bool>;
+
+		///Minimum amount of vote points (ayes, nays and abstentions) a proposal needs when it
+		/// finishes to get its deposit back. Use 0 to always return the deposit of finished
+		/// proposals.
+		#[pallet::constant]
+		type MinimumTurnout: Get<u32>;
+
+		///Minimum share of the registered voters that must vote on a proposal for its result to
+		/// count. Accounts that can only vote through `Eligibility` are part of the base once
+		/// they vote, until `RegistrationPeriod` after their last vote.
+		#[pallet::constant]
+		type QuorumVoters: Get<Perbill>;
+
+		///Minimum amount of vote points (ayes, nays and abstentions) a proposal needs for its
+		/// result to count.
+		#[pallet::constant]
+		type QuorumPoints: Get<u32>;
+
+		///Approval thresholds that proposals are allowed to use.
+		type ApprovalThresholds: Contains<ApprovalThreshold>;
+
+		///Handler for the balance slashed from registration and proposal deposits.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		///Cost of the points given to each choice of a vote, e.g. `Quadratic`.
+		type VoteCost: VoteCost<BalanceOf<Self>>;
+
+		///Price of one unit of vote cost when votes are paid with `Currency`, i.e. of one point
+		/// squared with the `Quadratic` cost.
+		#[pallet::constant]
+		type CostPerPointSquared: Get<BalanceOf<Self>>;
+
+		///Whether votes are paid by reserving or locking `Currency`, or with voice credits. With
+		/// `Lock`, the lock is recomputed from every vote still holding collateral, so keep
+		/// `MaxLockedVotes` low.
+		#[pallet::constant]
+		type CollateralMode: Get<CollateralMode>;
+
+		///Voice credits each voter gets per credit round when `CollateralMode` is `Credits`.
+		#[pallet::constant]
+		type CreditsPerRound: Get<u32>;
+
+		///Amount of blocks a credit round lasts. Voters get a new budget of `CreditsPerRound`
+		/// at the start of each round. `None` means there is a single round and credits are only
+		/// refilled by the registrar.
+		#[pallet::constant]
+		type CreditRoundLength: Get<Option<Self::BlockNumber>>;
+
 		///Period of time at the end of a proposal during which votes cannot be reduced or
 		/// cancelled.
 		type VoteRemovalThreshold: Get<u32>;
 
+		///Amount of blocks at the end of a secret ballot during which committed votes are
+		/// revealed. Votes can only be committed before it starts.
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		///Whether the cost of votes that were committed to a secret ballot but never revealed is
+		/// slashed once the ballot ends. Otherwise it is released like the cost of any vote. Only
+		/// reserved balance can be slashed.
+		#[pallet::constant]
+		type SlashUnrevealedVotes: Get<bool>;
+
+		///Alternative source of voting rights for accounts that are not registered, e.g. positive
+		/// identity judgements. Use `()` to only allow registered voters.
+		type Eligibility: VoterEligibility<Self::AccountId>;
+
+		///Amount of blocks a registration lasts before the voter has to be renewed. `None` means
+		/// registrations never expire.
+		#[pallet::constant]
+		type RegistrationPeriod: Get<Option<Self::BlockNumber>>;
+
 		///The limit of voter that can be registered to vote in the pallet.
 		type MaxVoters: Get<u32>;
 
 		///The limit of points an individual vote can have.
 		type VoteLimit: Get<u32>;
+
+		///The limit of votes a voter can have with balance still reserved. Balances of finished
+		/// proposals have to be unlocked before voting on new ones once the limit is reached.
+		#[pallet::constant]
+		type MaxLockedVotes: Get<u32>;
+
+		///The limit of proposals that can end at the same block. Proposals ending at a block are
+		/// closed in its `on_initialize`, so the calls attached to them must fit in a block
+		/// together: `MaxProposalsEndingPerBlock * MaxEnactmentWeight` is checked against the
+		/// maximum block weight in `integrity_test`.
+		#[pallet::constant]
+		type MaxProposalsEndingPerBlock: Get<u32>;
+
+		///The limit of accounts that can delegate their votes to the same voter.
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
+		///The longest chain of delegations an account can delegate into. `delegate` follows the
+		/// chain to reject cycles, so this bounds its cost.
+		#[pallet::constant]
+		type MaxDelegationDepth: Get<u32>;
+
+		///Signature of the votes signed off-chain and submitted with `submit_signed_votes`.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		///Public key of the signer of an off-chain vote, from which the voter is derived.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		///The maximum amount of signed votes submitted in a single batch.
+		#[pallet::constant]
+		type MaxSignedVotes: Get<u32>;
+
+		///Creates signed votes for the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: crate::BenchmarkHelper<Self::OffchainPublic, Self::OffchainSignature>;
+
+		///The maximum length of a proposal title, in bytes.
+		#[pallet::constant]
+		type MaxTitleLength: Get<u32>;
+
+		///The maximum length of a proposal summary, in bytes.
+		#[pallet::constant]
+		type MaxSummaryLength: Get<u32>;
+
+		///The maximum length of the URI pointing to the full proposal text, in bytes.
+		#[pallet::constant]
+		type MaxUriLength: Get<u32>;
+
+		///The maximum amount of options of a multi-option proposal.
+		#[pallet::constant]
+		type MaxOptions: Get<u32>;
+
+		///The maximum length of the name of a proposal option, in bytes.
+		#[pallet::constant]
+		type MaxOptionLength: Get<u32>;
+
+		///The overarching call type, used for the calls attached to proposals.
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		///Preimage provider holding the calls attached to proposals.
+		type Preimages: QueryPreimage + StorePreimage;
+
+		///Origin used to dispatch the call of a passed proposal.
+		type EnactmentOrigin: Get<Self::RuntimeOrigin>;
+
+		///Calls a passed proposal is allowed to dispatch. Others are reported as failed, so
+		/// voters only get the part of `EnactmentOrigin` the runtime entrusts them with.
+		type EnactableCalls: Contains<<Self as Config>::RuntimeCall>;
+
+		///The maximum weight of the call attached to a proposal, including fetching it from
+		/// the preimage provider. Heavier calls are not dispatched.
+		#[pallet::constant]
+		type MaxEnactmentWeight: Get<Weight>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
-	///Contains all users registered by the root that are eligible to vote.
+	///Contains all users registered by the registrar that are eligible to vote, and the details
+	/// of their registration.
 	#[pallet::storage]
-	pub type RegisteredVoters<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+	pub type RegisteredVoters<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Registration<T>>;
 
 	///Current amount of registered voters
 	#[pallet::storage]
 	pub type AmountVoters<T: Config> = StorageValue<_, u32>;
 
+	///Accounts that voted through `Eligibility` without being registered, and the block of their
+	/// last vote. They count toward the quorum base like registered voters.
+	#[pallet::storage]
+	pub type EligibleVoters<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+	///Current amount of eligible voters counted toward the quorum base, up to `MaxVoters`.
+	#[pallet::storage]
+	pub type AmountEligibleVoters<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	///Registered voters whose voting rights are temporarily suspended. Their votes stay counted
+	/// but cannot be modified until the proposals end.
+	#[pallet::storage]
+	pub type SuspendedVoters<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	///Registration requests waiting to be approved or rejected by the registrar, in the order they
+	/// were made.
+	#[pallet::storage]
+	pub type PendingRegistrations<T: Config> =
+		StorageValue<_, BoundedVec<RegistrationRequest<T>, T::MaxPendingRegistrations>, ValueQuery>;
+
 	///Holds user-made proposals, identified by a ProposalId, and the actual proposal data.
 	#[pallet::storage]
 	pub type Proposals<T: Config> = StorageMap<_, Blake2_128Concat, ProposalId, Proposal<T>>;
@@ -73,40 +335,225 @@ pub mod pallet {
 	///Holds the votes made by registered voters for a specific proposal. The first key is the
 	/// T::AccountId of the voter, and the second key is the ProposalId.
 	#[pallet::storage]
-	pub type Votes<T: Config> =
-		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, ProposalId, Vote>;
+	pub type Votes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProposalId,
+		VoteOf<T>,
+	>;
+
+	///Hidden votes committed to secret ballots and not revealed yet. The first key is the
+	/// T::AccountId of the voter, and the second key is the ProposalId.
+	#[pallet::storage]
+	#[pallet::getter(fn commitment_of)]
+	pub type Commitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProposalId,
+		CommitmentOf<T>,
+	>;
+
+	///Proposals for which a voter still has balance reserved, used to find the in-flight votes
+	/// of a voter without iterating all of their votes.
+	#[pallet::storage]
+	pub type LockedVotes<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ProposalId, T::MaxLockedVotes>,
+		ValueQuery,
+	>;
+
+	///Proposals in progress indexed by the block at which they end, so they can be closed
+	/// automatically.
+	#[pallet::storage]
+	pub type ProposalsEndingAt<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::BlockNumber,
+		BoundedVec<ProposalId, T::MaxProposalsEndingPerBlock>,
+		ValueQuery,
+	>;
+
+	///Voter each account delegates its votes to.
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_of)]
+	pub type Delegations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Delegation<T::AccountId>>;
+
+	///Accounts delegating their votes to each voter.
+	#[pallet::storage]
+	#[pallet::getter(fn delegators_of)]
+	pub type Delegators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxDelegators>,
+		ValueQuery,
+	>;
+
+	///Nonce the next vote signed off-chain by each voter must carry.
+	#[pallet::storage]
+	#[pallet::getter(fn signed_vote_nonce)]
+	pub type SignedVoteNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	///Balance locked from each voter to back their votes, when votes are backed by a lock.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_lock)]
+	pub type VoteLocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	///Voice credits left to each voter, when votes are paid with credits. Voters without an
+	/// entry for the current round have the full `CreditsPerRound` budget.
+	#[pallet::storage]
+	pub type VoiceCredits<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, CreditBalance>;
 
 	///Holds the counter used to increase the ProposalId of proposals.
 	#[pallet::storage]
 	pub type ProposalCounter<T: Config> = StorageValue<_, ProposalId>;
 
+	///Where the on_idle sweeper continues in the next block.
+	#[pallet::storage]
+	pub type NextSweep<T: Config> = StorageValue<_, SweepCursor<T::AccountId>, ValueQuery>;
+
+	///Every proposal up to this ProposalId is closed, so the on_idle sweeper does not check
+	/// them again.
+	#[pallet::storage]
+	pub type SweptProposals<T: Config> = StorageValue<_, ProposalId, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		///Accounts registered as voters from genesis.
+		pub voters: Vec<T::AccountId>,
+		///Proposals open from genesis, as (proposer, description, end block). The proposer must
+		/// be one of the genesis voters. They are created without title, summary, URI or options.
+		pub proposals: Vec<(T::AccountId, T::Hash, T::BlockNumber)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { voters: Default::default(), proposals: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				self.voters.len() as u32 <= T::MaxVoters::get(),
+				"Genesis voters exceed the MaxVoters limit"
+			);
+			for voter in &self.voters {
+				assert!(!<RegisteredVoters<T>>::contains_key(voter), "Duplicate voter in genesis");
+				<RegisteredVoters<T>>::insert(voter, Registration::<T>::new(None));
+			}
+			<AmountVoters<T>>::put(self.voters.len() as u32);
+
+			for (proposer, description, time_period) in &self.proposals {
+				assert!(Pallet::<T>::is_registered(proposer), "Genesis proposer is not a voter");
+				assert!(
+					*time_period > <frame_system::Pallet<T>>::block_number(),
+					"Genesis proposal must end after genesis"
+				);
+				Pallet::<T>::create_proposal(
+					proposer.clone(),
+					*description,
+					ProposalParamsOf::<T>::default(),
+					*time_period,
+					None,
+					Zero::zero(),
+				)
+				.expect("Genesis proposals must fit in the proposal counter and end queue");
+			}
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		///New voter 'T::AccountId' registered by root into the RegisteredVoters list.
+		///New voter 'T::AccountId' registered by the registrar into the RegisteredVoters list.
 		VoterRegistered { who: T::AccountId },
+		///Voter removed from the RegisteredVoters list. Votes on ongoing proposals were canceled.
+		VoterRemoved { who: T::AccountId },
+		///Voter rights suspended. Existing votes are frozen until their proposals end.
+		VoterSuspended { who: T::AccountId },
+		///Suspended voter got their voting rights back.
+		VoterReinstated { who: T::AccountId },
+		///The registration of a voter was extended until 'expires_at'.
+		RegistrationRenewed { who: T::AccountId, expires_at: Option<T::BlockNumber> },
+		///The registration of a voter expired and was removed. It no longer counts toward
+		/// MaxVoters or the quorum.
+		RegistrationExpired { who: T::AccountId },
+		///The registrar set the voice credits of a voter for the current round.
+		CreditsIssued { who: T::AccountId, round: u32, credits: u32 },
+		///An account asked to be registered as a voter and reserved 'deposit'.
+		RegistrationRequested { who: T::AccountId, deposit: BalanceOf<T> },
+		///The registrar approved a registration request. The deposit was returned.
+		RegistrationApproved { who: T::AccountId },
+		///The registrar rejected a registration request. The deposit was slashed if 'slashed'
+		/// is true, otherwise it was returned.
+		RegistrationRejected { who: T::AccountId, slashed: bool },
 		///A user submitted a new proposal
-		ProposalSubmitted { proposal_id: ProposalId, who: T::AccountId },
+		ProposalSubmitted {
+			proposal_id: ProposalId,
+			who: T::AccountId,
+			title: BoundedVec<u8, T::MaxTitleLength>,
+		},
 		///A registered voter casted a vote for a specific proposal
 		VoteCasted { proposal_id: ProposalId, who: T::AccountId },
 		///Registered voter updated their vote for Proposal ID from 'previous' to 'new' decision.
 		VoteUpdated {
 			proposal_id: ProposalId,
 			who: T::AccountId,
-			previous: VoteDecision,
-			new: VoteDecision,
+			previous: VoteDecisionOf<T>,
+			new: VoteDecisionOf<T>,
+		},
+		///A voter committed a hidden vote to a secret ballot.
+		VoteCommitted { proposal_id: ProposalId, who: T::AccountId },
+		///A voter revealed the vote they committed to a secret ballot.
+		VoteRevealed {
+			proposal_id: ProposalId,
+			who: T::AccountId,
+			vote_decision: VoteDecisionOf<T>,
 		},
+		///A commitment that was never revealed was removed and its cost released, or slashed if
+		/// 'slashed' is true.
+		CommitmentRemoved { proposal_id: ProposalId, who: T::AccountId, slashed: bool },
 		///A voter canceled his vote for an ongoing proposal
 		VoteCanceled { proposal_id: ProposalId, who: T::AccountId },
 		///Proposal ended and result is defined
 		ProposalEnded { proposal_id: ProposalId, status: ProposalStatus },
+		///Final points of each option of a multi-option proposal that ended.
+		OptionsTallied { proposal_id: ProposalId, tallies: BoundedVec<u32, T::MaxOptions> },
 		///Proposal end time updated for Proposal ID: 'ProposalId' with new end block as
 		/// 'T::BlockNumber'
 		ProposalUpdated { proposal_id: ProposalId, end_block: T::BlockNumber },
 		///Proposal canceled by the proposer
 		ProposalCanceled { proposal_id: ProposalId },
+		///The deposit of a proposal was slashed because it was canceled or did not reach the
+		/// minimum turnout.
+		ProposalDepositSlashed { proposal_id: ProposalId, amount: BalanceOf<T> },
 		///User unlocked balance of a specific proposal
 		BalanceUnlocked { proposal_id: ProposalId, who: T::AccountId },
+		///The call attached to a passed proposal was dispatched with the given result.
+		ProposalEnacted { proposal_id: ProposalId, result: DispatchResult },
+		///A voter delegated their votes on the proposals in 'scope' to 'to'.
+		Delegated { who: T::AccountId, to: T::AccountId, scope: DelegationScope },
+		///A voter stopped delegating their votes.
+		Undelegated { who: T::AccountId },
+		///The vote of 'delegate' was cast on behalf of 'who'.
+		DelegatedVoteCast { proposal_id: ProposalId, delegate: T::AccountId, who: T::AccountId },
+		///A vote signed off-chain by 'who' could not be applied.
+		SignedVoteRejected { proposal_id: ProposalId, who: T::AccountId, error: DispatchError },
+		///A batch of signed votes was submitted. 'cast' votes were applied and 'rejected' were
+		/// not.
+		SignedVotesSubmitted { proposal_id: ProposalId, cast: u32, rejected: u32 },
 	}
 
 	#[pallet::error]
@@ -117,6 +564,50 @@ pub mod pallet {
 		VoterIsNotRegistered,
 		///Maximum registered voters limit has been reached.
 		MaxVotersLimitReached,
+		///Voter is suspended and cannot vote or modify their votes.
+		VoterSuspended,
+		///Voter is already suspended.
+		AlreadySuspended,
+		///Voter is not suspended.
+		NotSuspended,
+		///The voter has too many votes with reserved balance. Unlock finished proposals first.
+		TooManyLockedVotes,
+		///The proposal title is longer than MaxTitleLength.
+		TitleTooLong,
+		///The proposal summary is longer than MaxSummaryLength.
+		SummaryTooLong,
+		///The proposal URI is longer than MaxUriLength.
+		UriTooLong,
+		///A multi-option proposal needs at least two options.
+		TooFewOptions,
+		///The proposal has more than MaxOptions options.
+		TooManyOptions,
+		///The name of an option is longer than MaxOptionLength.
+		OptionTooLong,
+		///Multi-option proposals cannot have a call or an approval threshold other than simple
+		/// majority.
+		InvalidOptionsProposal,
+		///The vote does not match the proposal: aye, nay or split for aye/nay proposals, or one
+		/// amount of points per option for multi-option proposals.
+		InvalidVoteOptions,
+		///The call attached to the proposal is heavier than MaxEnactmentWeight.
+		EnactmentWeightTooHigh,
+		///The call attached to the proposal is not one of the EnactableCalls.
+		CallNotEnactable,
+		///Too many proposals end at the requested block. Choose a different end block.
+		TooManyProposalsEnding,
+		///The approval threshold is not allowed by the runtime.
+		ThresholdNotAllowed,
+		///The account already has a pending registration request.
+		RegistrationAlreadyRequested,
+		///No pending registration request was found for the account.
+		RegistrationRequestNotFound,
+		///The queue of pending registration requests is full.
+		TooManyPendingRegistrations,
+		///The voter does not have enough voice credits left in this round.
+		NotEnoughCredits,
+		///The voter's free balance cannot cover the lock backing their votes.
+		InsufficientFunds,
 		///Voter's vote for the proposal is already registered.
 		VoteAlreadyCasted,
 		///Vote not found for user and proposal
@@ -145,6 +636,70 @@ pub mod pallet {
 		ProposalInProgress,
 		///Overflow when performing an operation
 		Overflow,
+		///Votes can only be delegated to registered voters.
+		DelegateNotRegistered,
+		///The delegate delegates their votes back to the caller, directly or through others.
+		DelegationCycle,
+		///The chain of delegations starting at the delegate is longer than MaxDelegationDepth.
+		DelegationTooDeep,
+		///The delegate already has MaxDelegators delegators.
+		TooManyDelegators,
+		///The caller does not delegate their votes.
+		NotDelegating,
+		///The batch has more than MaxSignedVotes signed votes.
+		TooManySignedVotes,
+		///The signature does not match the vote and the voter.
+		InvalidSignature,
+		///The nonce of the signed vote is not the next nonce of the voter.
+		InvalidNonce,
+		///Votes on secret ballots must be committed and revealed, and cannot be updated or
+		/// canceled.
+		SecretBallot,
+		///The proposal is not a secret ballot.
+		NotSecretBallot,
+		///The commit phase of the secret ballot is over.
+		CommitPhaseEnded,
+		///The secret ballot is not in its reveal window.
+		NotRevealPhase,
+		///No commitment was found for the voter and proposal.
+		CommitmentNotFound,
+		///The revealed vote and salt do not match the commitment.
+		InvalidReveal,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		///Closes the proposals ending at this block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = <ProposalsEndingAt<T>>::take(now);
+			let mut weight = T::WeightInfo::close_proposals(ending.len() as u32);
+			for proposal_id in ending {
+				if let Some(proposal) = Self::get_proposal(&proposal_id) {
+					if proposal.status == ProposalStatus::InProgress {
+						weight = weight.saturating_add(Self::do_finish_proposal(proposal));
+					}
+				}
+			}
+			weight
+		}
+
+		///Uses the remaining block weight to finalize proposals that ended and to unlock the
+		/// balance of votes on finished proposals.
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::sweep(now, remaining_weight)
+		}
+
+		///Checks that closing a full queue of proposals, with the heaviest call each, fits in a
+		/// block.
+		fn integrity_test() {
+			let max_ending = T::MaxProposalsEndingPerBlock::get();
+			let closing = T::WeightInfo::close_proposals(max_ending)
+				.saturating_add(T::MaxEnactmentWeight::get().saturating_mul(max_ending.into()));
+			assert!(
+				closing.all_lte(T::BlockWeights::get().max_block),
+				"MaxProposalsEndingPerBlock * MaxEnactmentWeight must fit in a block"
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -152,61 +707,294 @@ pub mod pallet {
 		/// Registers a voter into the list of registered voters
 		/// if they have not already been registered
 		/// or if the maximum number of voters has not been reached.
+		/// An expired registration is replaced by a new one in the same slot.
 		///
-		/// Origin must be root user.
+		/// Origin must be `RegistrarOrigin`.
 		#[pallet::call_index(0)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::register_voter())]
 		pub fn register_voter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin)?;
-			ensure!(!Self::is_registered(&who), Error::<T>::AlreadyRegistered);
+			T::RegistrarOrigin::ensure_origin(origin.clone())?;
+			Self::do_register_voter(who, ensure_signed(origin).ok())
+		}
 
-			let amount_voters: u32 = <AmountVoters<T>>::try_get().unwrap_or_default();
-			ensure!(amount_voters < T::MaxVoters::get(), Error::<T>::MaxVotersLimitReached);
+		/// Extends the registration of a voter by `RegistrationPeriod`.
+		///
+		/// The new period starts at the current expiry block, or at the current block if the
+		/// registration already expired. Origin must be `RegistrarOrigin`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::renew_registration())]
+		pub fn renew_registration(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+
+			let expires_at = <RegisteredVoters<T>>::try_mutate(&who, |registration| {
+				let registration = registration.as_mut().ok_or(Error::<T>::VoterIsNotRegistered)?;
+				registration.renew();
+				Ok::<_, DispatchError>(registration.expires_at)
+			})?;
+
+			Self::deposit_event(Event::RegistrationRenewed { who, expires_at });
+			Ok(())
+		}
 
-			//Register voter and increase voter counter
-			<RegisteredVoters<T>>::insert(who.clone(), ());
-			<AmountVoters<T>>::put(amount_voters.saturating_add(1));
+		/// Sets the voice credits of a voter for the current credit round.
+		///
+		/// Used to hand out budgets when `CreditRoundLength` is `None`, or to correct the budget
+		/// of a single voter. Credits are only used when `CollateralMode` is `Credits`. Origin
+		/// must be `RegistrarOrigin`.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::issue_credits())]
+		pub fn issue_credits(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			credits: u32,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
 
-			Self::deposit_event(Event::VoterRegistered { who });
+			let round = Self::current_round();
+			<VoiceCredits<T>>::insert(&who, CreditBalance { round, credits });
+
+			Self::deposit_event(Event::CreditsIssued { who, round, credits });
+			Ok(())
+		}
+
+		/// Asks the registrar to register the caller as a voter.
+		///
+		/// Reserves `RegistrationDeposit` from the caller and adds the request to the queue of
+		/// pending registrations. The deposit is returned when the request is approved.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::request_registration())]
+		pub fn request_registration(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			//An expired registration that was not pruned yet can be requested again.
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				!<RegisteredVoters<T>>::get(&who).map_or(false, |r| !r.is_expired(now)),
+				Error::<T>::AlreadyRegistered
+			);
+
+			let mut pending = <PendingRegistrations<T>>::get();
+			ensure!(
+				!pending.iter().any(|request| request.who == who),
+				Error::<T>::RegistrationAlreadyRequested
+			);
+
+			let deposit = T::RegistrationDeposit::get();
+			let request = RegistrationRequest::<T>::new(who.clone(), deposit);
+			pending.try_push(request).map_err(|_| Error::<T>::TooManyPendingRegistrations)?;
+			T::Currency::reserve_named(&T::ReserveId::get(), &who, deposit)?;
+			<PendingRegistrations<T>>::put(pending);
+
+			Self::deposit_event(Event::RegistrationRequested { who, deposit });
+			Ok(())
+		}
+
+		/// Approves a pending registration request, registering the account as a voter and
+		/// returning its deposit.
+		///
+		/// Origin must be `RegistrarOrigin`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::approve_registration())]
+		pub fn approve_registration(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin.clone())?;
+
+			let request = Self::take_registration_request(&who)?;
+			T::Currency::unreserve_named(&T::ReserveId::get(), &who, request.deposit);
+			Self::do_register_voter(who.clone(), ensure_signed(origin).ok())?;
+
+			Self::deposit_event(Event::RegistrationApproved { who });
+			Ok(())
+		}
+
+		/// Rejects a pending registration request. The deposit is slashed if `slash` is true,
+		/// otherwise it is returned to the account.
+		///
+		/// Origin must be `RegistrarOrigin`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::reject_registration())]
+		pub fn reject_registration(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			slash: bool,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+
+			let request = Self::take_registration_request(&who)?;
+			if slash {
+				let (imbalance, _) =
+					T::Currency::slash_reserved_named(&T::ReserveId::get(), &who, request.deposit);
+				T::Slash::on_unbalanced(imbalance);
+			} else {
+				T::Currency::unreserve_named(&T::ReserveId::get(), &who, request.deposit);
+			}
+
+			Self::deposit_event(Event::RegistrationRejected { who, slashed: slash });
+			Ok(())
+		}
+
+		/// Removes a voter from the list of registered voters.
+		///
+		/// Votes of the voter on proposals that are still open are canceled: the tally of the
+		/// proposal is corrected and the reserved balance is returned. Votes on proposals that
+		/// already ended are kept so the voter can still unlock their balance. Delegations from
+		/// and to the voter are removed.
+		///
+		/// Origin must be `RemovalOrigin`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::remove_voter(
+			T::MaxLockedVotes::get(),
+			T::MaxDelegators::get()
+		))]
+		pub fn remove_voter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResultWithPostInfo {
+			T::RemovalOrigin::ensure_origin(origin)?;
+			ensure!(<RegisteredVoters<T>>::contains_key(&who), Error::<T>::VoterIsNotRegistered);
+
+			let locked_votes = <LockedVotes<T>>::get(&who);
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			for proposal_id in locked_votes.iter() {
+				let proposal =
+					Self::get_proposal(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+				if proposal.time_period < current_block_number ||
+					proposal.status != ProposalStatus::InProgress
+				{
+					continue
+				}
+				if let Some(commitment) = <Commitments<T>>::get(&who, proposal_id) {
+					Self::do_remove_commitment(&who, &proposal, commitment)?;
+					continue
+				}
+				let vote: VoteOf<T> =
+					<Votes<T>>::try_get(&who, proposal_id).ok().ok_or(Error::<T>::VoteNotFound)?;
+				Self::do_cancel_vote(&who, *proposal_id, proposal, vote)?;
+			}
+
+			let delegators = Self::clear_registration(&who);
+
+			Self::deposit_event(Event::VoterRemoved { who });
+			Ok(Some(T::WeightInfo::remove_voter(locked_votes.len() as u32, delegators)).into())
+		}
+
+		/// Suspends the voting rights of a registered voter.
+		///
+		/// The voter keeps their place in the list of registered voters, but cannot propose,
+		/// vote, update or cancel votes until reinstated. Existing votes stay counted and their
+		/// balance can be unlocked once the proposals end. Voters that are only eligible through
+		/// `Eligibility` can be suspended too.
+		///
+		/// Origin must be `RemovalOrigin`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::suspend_voter())]
+		pub fn suspend_voter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::RemovalOrigin::ensure_origin(origin)?;
+			ensure!(
+				<RegisteredVoters<T>>::contains_key(&who) || T::Eligibility::is_eligible(&who),
+				Error::<T>::VoterIsNotRegistered
+			);
+			ensure!(!Self::is_suspended(&who), Error::<T>::AlreadySuspended);
+
+			<SuspendedVoters<T>>::insert(&who, ());
+
+			Self::deposit_event(Event::VoterSuspended { who });
+			Ok(())
+		}
+
+		/// Gives a suspended voter their voting rights back.
+		///
+		/// Origin must be `RemovalOrigin`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::reinstate_voter())]
+		pub fn reinstate_voter(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::RemovalOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_suspended(&who), Error::<T>::NotSuspended);
+
+			<SuspendedVoters<T>>::remove(&who);
+
+			Self::deposit_event(Event::VoterReinstated { who });
 			Ok(())
 		}
 
 		/// Creates a new proposal for voting.
-		/// The proposal contains a hashed description and a voting time limit in blocks.
+		/// The proposal contains a hashed description and a voting time limit in blocks. Its
+		/// `ProposalParams` hold a title, a summary and a URI to the full text that wallets can
+		/// display, along with the options, threshold and kind of ballot described below.
+		///
+		/// The threshold sets the share of the points the ayes need for the proposal to pass. It
+		/// must be one of the `ApprovalThresholds` allowed by the runtime.
+		///
+		/// An optional call can be attached, usually as a preimage hash and length. It is
+		/// dispatched with `EnactmentOrigin` if the proposal passes and the call is one of the
+		/// `EnactableCalls`.
 		///
-		/// Only registered voters can create proposals.
+		/// With no options, voters vote aye or nay. With two to `MaxOptions` options, voters
+		/// spread their points across the options and the option with the most points wins.
+		/// Multi-option proposals cannot carry a call and always use a simple majority.
+		///
+		/// On secret ballots, voters commit a hash of their vote until the last `RevealPeriod`
+		/// blocks of the proposal, and reveal it during those blocks. Only revealed votes are
+		/// counted.
+		///
+		/// Only registered voters can create proposals. `ProposalDeposit` is reserved from the
+		/// proposer until the proposal ends.
 		#[pallet::call_index(1)]
-		#[pallet::weight(0)]
+		#[pallet::weight(
+			T::WeightInfo::make_proposal().max(T::WeightInfo::make_options_proposal())
+		)]
 		pub fn make_proposal(
 			origin: OriginFor<T>,
 			description: T::Hash,
+			params: ProposalParams,
 			time_period: T::BlockNumber,
+			call: Option<BoundedCallOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
+			ensure!(
+				T::ApprovalThresholds::contains(&params.threshold),
+				Error::<T>::ThresholdNotAllowed
+			);
+
+			let params = Self::bound_params(params)?;
+			if !params.options.is_empty() {
+				ensure!(
+					call.is_none() && params.threshold == ApprovalThreshold::SimpleMajority,
+					Error::<T>::InvalidOptionsProposal
+				);
+			}
 
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 			ensure!(time_period > current_block_number, Error::<T>::TimePeriodToLow);
+			if params.secret {
+				ensure!(
+					time_period > current_block_number.saturating_add(T::RevealPeriod::get()),
+					Error::<T>::TimePeriodToLow
+				);
+			}
 
-			let mut proposal_id: ProposalId = ProposalCounter::<T>::get().unwrap_or_default();
-			ensure!(proposal_id.checked_add(1).is_some(), Error::<T>::ProposalIdToHigh);
-			proposal_id = proposal_id + 1;
-
-			let new_proposal =
-				Proposal::<T>::new(proposal_id, who.clone(), description, time_period);
-
-			<Proposals<T>>::insert(proposal_id, new_proposal);
-			<ProposalCounter<T>>::put(proposal_id);
-			Self::deposit_event(Event::ProposalSubmitted { proposal_id, who });
+			let deposit = T::ProposalDeposit::get();
+			T::Currency::reserve_named(&T::ReserveId::get(), &who, deposit)?;
+			let title = params.title.clone();
+			let proposal_id = Self::create_proposal(
+				who.clone(),
+				description,
+				params,
+				time_period,
+				call.clone(),
+				deposit,
+			)?;
+			if let Some(call) = &call {
+				T::Preimages::hold(call);
+			}
+			Self::deposit_event(Event::ProposalSubmitted { proposal_id, who, title });
 
 			Ok(())
 		}
 
 		/// Extends the voting period of a proposal by increasing its time limit in blocks.
 		///
-		/// Only the user who created the proposal can call this extrinsic.
+		/// Only the user who created the proposal can call this extrinsic, while the proposal is
+		/// in progress. Secret ballots cannot be extended once their reveal window started.
 		#[pallet::call_index(2)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::increase_proposal_time())]
 		pub fn increase_proposal_time(
 			origin: OriginFor<T>,
 			proposal_id: ProposalId,
@@ -217,11 +1005,21 @@ pub mod pallet {
 
 			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
 			ensure!(proposal.proposer == who, Error::<T>::Unauthorized);
+			ensure!(
+				proposal.status == ProposalStatus::InProgress,
+				Error::<T>::ProposalAlreadyEnded
+			);
 
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 			ensure!(new_time_period > proposal.time_period, Error::<T>::TimePeriodToLow);
 			ensure!(new_time_period > current_block_number, Error::<T>::TimePeriodToLow);
+			ensure!(
+				!proposal.secret || current_block_number < proposal.reveal_start(),
+				Error::<T>::CommitPhaseEnded
+			);
 
+			Self::unschedule_end(proposal.time_period, proposal_id);
+			Self::schedule_end(new_time_period, proposal_id)?;
 			<Proposals<T>>::mutate(proposal_id, |proposal| {
 				if let Some(p) = proposal.as_mut() {
 					p.time_period = new_time_period
@@ -235,9 +1033,10 @@ pub mod pallet {
 
 		/// Cancel a proposal if it hasn't ended yet
 		///
-		/// The proposal can only be cancelled by the user who created it.
+		/// The proposal can only be cancelled by the user who created it. The deposit is slashed
+		/// if `SlashCanceledProposals` is set, otherwise it is returned.
 		#[pallet::call_index(3)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::cancel_proposal())]
 		pub fn cancel_proposal(origin: OriginFor<T>, proposal_id: ProposalId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -257,6 +1056,11 @@ pub mod pallet {
 					p.status = ProposalStatus::Canceled
 				}
 			});
+			Self::unschedule_end(proposal.time_period, proposal_id);
+			Self::settle_proposal_deposit(&proposal, T::SlashCanceledProposals::get());
+			if let Some(call) = &proposal.call {
+				T::Preimages::drop(call);
+			}
 			Self::deposit_event(Event::ProposalCanceled { proposal_id });
 
 			Ok(())
@@ -264,90 +1068,60 @@ pub mod pallet {
 
 		/// Allows a registered voter to vote on a proposal if it's still ongoing. The vote
 		/// increases the ayes or nays votes of the proposal based on the number of vote points.
+		/// A split vote divides its points between both, and costs as much as all of its points
+		/// cast on one side.
+		/// On multi-option proposals the points can be spread across several options. Abstaining
+		/// counts the voter and their points toward quorum and turnout, but not toward the result.
 
 		/// To vote, the user must reserve the balance of their account, equal to the square
-		/// of the number of votes they want to cast.
+		/// of the number of votes they want to cast, summed over the options they vote for. When
+		/// `CollateralMode` is `Credits` the cost is paid with voice credits instead.
+		///
+		/// The vote is also cast on behalf of the delegators of the voter that have not voted on
+		/// the proposal, each paying their own cost. Delegators that cannot pay are skipped. A
+		/// delegator that votes after their delegate replaces the vote cast on their behalf.
 		// The number of votes must be greater than zero and less than the VoteLimit.
 		#[pallet::call_index(4)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::vote(T::MaxDelegators::get()))]
 		pub fn vote(
 			origin: OriginFor<T>,
 			proposal_id: ProposalId,
-			vote_decision: VoteDecision,
+			vote_decision: VoteDecisionOf<T>,
 		) -> DispatchResult {
-			//Verify sender is part of register voters
 			let who: T::AccountId = ensure_signed(origin)?;
-			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
-
-			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
-
-			//Check that propossal is not passed removal_treshold
-			let current_block_number = <frame_system::Pallet<T>>::block_number();
-			ensure!(
-				proposal.time_period > current_block_number &&
-					proposal.status == ProposalStatus::InProgress,
-				Error::<T>::ProposalAlreadyEnded
-			);
-
-			//Verify if voter already casted vote
-			ensure!(!Self::vote_casted(&who, &proposal_id), Error::<T>::VoteAlreadyCasted);
-
-			let vote_amount = match vote_decision {
-				VoteDecision::Aye(v) => v,
-				VoteDecision::Nay(v) => v,
-			};
-
-			ensure!(vote_amount > 0, Error::<T>::InvalidVoteAmount);
-			ensure!(vote_amount <= T::VoteLimit::get(), Error::<T>::VoteAmountLimit);
-
-			//Reserve balance corresponding to vote amount^2.
-			let amount_to_reserve: u32 =
-				(vote_amount).checked_pow(2).ok_or(Error::<T>::Overflow)?;
-			T::Currency::reserve(&who, amount_to_reserve.into())?;
-
-			let vote = Vote { vote_decision: vote_decision.clone(), locked: true };
-
-			//Insert vote and update proposals
-			<Votes<T>>::insert(who.clone(), proposal_id, vote.clone());
-
-			<Proposals<T>>::mutate(proposal_id, |proposal| {
-				if let Some(p) = proposal.as_mut() {
-					match vote_decision {
-						VoteDecision::Aye(v) => p.ayes += v,
-						VoteDecision::Nay(v) => p.nays += v,
-					}
-				}
-			});
-
-			Self::deposit_event(Event::VoteCasted { proposal_id, who });
-			Ok(())
+			Self::cast_vote(&who, proposal_id, vote_decision)
 		}
 
 		/// Updates the vote of a voter in a proposal with a new amount of points and the ability
-		/// to switch between aye and nay.
+		/// to switch between aye and nay, or between options.
 		///
 		/// The function performs several checks and updates the balance of the user and the
 		/// vote count of the proposal if the new vote differs from the original.
 		///
 		/// - Check that the proposal is still in progress and has not passed the removal threshold.
-		///   If the threshold is surppased the voter cant reduce the amount of votes.
+		///   If the threshold is surppased the voter cant reduce the cost of their vote. The cost
+		///   of a split vote depends on its total points, so moving points between sides, or
+		///   between a split and a single-sided vote, is only a reduction if the total drops.
 		/// - Calculate the new amount of vote points and update the aye or nay count accordingly.
 		/// - Reserve or unreserve the user's balance based on the comparison between the current
 		///   and new vote amounts.
 		/// - Update the vote record in storage and emit an event for the vote update.
+		/// - Update the votes cast on behalf of the delegators of the voter the same way. A
+		///   delegator that cannot pay for the new vote keeps their previous one.
+		///
+		/// A vote cast on behalf of a delegator becomes their own vote once they update it.
 		#[pallet::call_index(5)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::update_vote(T::MaxDelegators::get()))]
 		pub fn update_vote(
 			origin: OriginFor<T>,
 			proposal_id: ProposalId,
-			new_vote_decision: VoteDecision,
+			new_vote_decision: VoteDecisionOf<T>,
 		) -> DispatchResult {
 			//Verify sender is part of register voters and vote exists
 			let who: T::AccountId = ensure_signed(origin)?;
 			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
 
-			let mut proposal =
-				Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
 			//Check that propossal is not passed removal_treshold
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 			ensure!(
@@ -355,69 +1129,28 @@ pub mod pallet {
 					proposal.status == ProposalStatus::InProgress,
 				Error::<T>::ProposalAlreadyEnded
 			);
+			ensure!(!proposal.secret, Error::<T>::SecretBallot);
 
 			//Get vote and verify if it exists
 			let current_vote =
 				<Votes<T>>::try_get(&who, &proposal_id).ok().ok_or(Error::<T>::VoteNotFound)?;
 
-			let current_amount: u32 = match current_vote.vote_decision {
-				VoteDecision::Aye(v) => {
-					proposal.ayes = proposal.ayes.saturating_sub(v);
-					v
-				},
-				VoteDecision::Nay(v) => {
-					proposal.nays = proposal.nays.saturating_sub(v);
-					v
-				},
-			};
-
-			let new_amount = match new_vote_decision {
-				VoteDecision::Aye(v) => {
-					proposal.ayes += v;
-					v
-				},
-				VoteDecision::Nay(v) => {
-					proposal.nays += v;
-					v
-				},
-			};
-			if new_amount.cmp(&current_amount) == Ordering::Less {
-				//Check threshold
-				ensure!(
-					!Self::passed_removal_threshold(&proposal.time_period),
-					Error::<T>::PassedRemovalThreshold
-				);
-			}
-
-			ensure!(new_amount != 0, Error::<T>::InvalidUpdateAmount);
-			ensure!(new_amount <= T::VoteLimit::get(), Error::<T>::VoteAmountLimit);
-
-			let current_amount_pow: u32 =
-				current_amount.checked_pow(2).ok_or(Error::<T>::Overflow)?;
-			let new_amount_pow: u32 = new_amount.checked_pow(2).ok_or(Error::<T>::Overflow)?;
-
-			//Modify reserved amount
-			match new_amount.cmp(&current_amount) {
-				Ordering::Greater => {
-					T::Currency::reserve(&who, (new_amount_pow - current_amount_pow).into())?;
-				},
-				Ordering::Less => {
-					T::Currency::unreserve(&who, (current_amount_pow - new_amount_pow).into());
-				},
-				_ => (),
-			};
-
-			let new_vote = Vote { vote_decision: new_vote_decision, locked: true };
-
-			<Votes<T>>::insert(who.clone(), proposal_id, new_vote.clone());
-			<Proposals<T>>::insert(proposal_id, proposal);
+			let new_vote = Self::do_update_vote(
+				&who,
+				proposal,
+				current_vote.clone(),
+				new_vote_decision,
+				false,
+			)?;
 			Self::deposit_event(Event::VoteUpdated {
 				proposal_id,
-				who,
+				who: who.clone(),
 				previous: current_vote.vote_decision,
-				new: new_vote.vote_decision,
+				new: new_vote.vote_decision.clone(),
 			});
 
+			Self::update_delegated_votes(&who, proposal_id, &new_vote.vote_decision);
+
 			Ok(())
 		}
 
@@ -426,16 +1159,18 @@ pub mod pallet {
 		///
 		/// It then updates the count of votes in favor (ayes) or against (nays) accordingly.
 		///
-		/// Returns the reserved balance to the voter
+		/// Returns the reserved balance to the voter. The votes cast on behalf of their delegators
+		/// are canceled too.
 		#[pallet::call_index(9)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::cancel_vote(T::MaxDelegators::get()))]
 		pub fn cancel_vote(origin: OriginFor<T>, proposal_id: ProposalId) -> DispatchResult {
 			let who: T::AccountId = ensure_signed(origin)?;
+			ensure!(!Self::is_suspended(&who), Error::<T>::VoterSuspended);
 			//Allows to calculate treshold
 
-			let mut proposal =
-				Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
-			let vote: Vote = <Votes<T>>::try_get(who.clone(), proposal_id)
+			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(!proposal.secret, Error::<T>::SecretBallot);
+			let vote: VoteOf<T> = <Votes<T>>::try_get(who.clone(), proposal_id)
 				.ok()
 				.ok_or(Error::<T>::VoteNotFound)?;
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
@@ -452,44 +1187,37 @@ pub mod pallet {
 				Error::<T>::PassedRemovalThreshold
 			);
 
-			match vote.vote_decision {
-				VoteDecision::Aye(v) => proposal.ayes = proposal.ayes.saturating_sub(v),
-				VoteDecision::Nay(v) => proposal.nays = proposal.nays.saturating_sub(v),
-			}
-
-			<Proposals<T>>::insert(proposal_id, proposal);
-			<Votes<T>>::remove(who.clone(), proposal_id);
-
-			let vote_amount = match vote.vote_decision {
-				VoteDecision::Aye(v) => v,
-				VoteDecision::Nay(v) => v,
-			};
-
-			//unreserve balance corresponding to the vote (amount^2).
-			let amount_to_unreserve: u32 =
-				(vote_amount).checked_pow(2).ok_or(Error::<T>::Overflow)?;
-			T::Currency::unreserve(&who, amount_to_unreserve.into());
-
-			Self::deposit_event(Event::VoteCanceled { proposal_id, who });
-
-			Ok(())
-		}
+			Self::do_cancel_vote(&who, proposal_id, proposal, vote)?;
+			Self::remove_delegated_votes(&who, proposal_id);
+			Ok(())
+		}
 
 		/// Finishes a proposal by calculating the result based on the number of ayes and nays.
 		///
 		/// The proposal can only be finished if the time limit (in blocks) has been
-		/// exceeded and the status of the proposal is 'In Progress'.
+		/// exceeded and the status of the proposal is 'In Progress'. The deposit of the proposer
+		/// is returned, or slashed if the proposal did not reach `MinimumTurnout`. If the proposal
+		/// passed, its call is dispatched.
+		///
+		/// Proposals are closed automatically at the start of their end block, so this is only
+		/// a fallback for proposals that were left open. Votes cast on behalf of delegators are
+		/// part of the tally like any other vote. Commitments to secret ballots that were not
+		/// revealed are not counted.
 		///
 		/// This extrinsic can be called by any registered voter.
 		#[pallet::call_index(7)]
-		#[pallet::weight(0)]
-		pub fn finish_proposal(origin: OriginFor<T>, proposal_id: ProposalId) -> DispatchResult {
+		#[pallet::weight(
+			T::WeightInfo::finish_proposal().saturating_add(T::MaxEnactmentWeight::get())
+		)]
+		pub fn finish_proposal(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+		) -> DispatchResultWithPostInfo {
 			//Verify sender is part of register voters and vote exists
 			let who: T::AccountId = ensure_signed(origin)?;
 			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
 
-			let mut proposal =
-				Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
 
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 			ensure!(
@@ -498,58 +1226,229 @@ pub mod pallet {
 				Error::<T>::ProposalAlreadyEnded
 			);
 
-			let voting_result: ProposalStatus = match proposal.ayes.cmp(&proposal.nays) {
-				Ordering::Less => ProposalStatus::Rejected,
-				Ordering::Greater => ProposalStatus::Passed,
-				Ordering::Equal => ProposalStatus::Tied,
-			};
-
-			proposal.status = voting_result.clone();
-
-			<Proposals<T>>::insert(proposal_id, proposal);
-			Self::deposit_event(Event::ProposalEnded { proposal_id, status: voting_result });
-			Ok(())
+			let enactment_weight = Self::do_finish_proposal(proposal);
+			Ok(Some(T::WeightInfo::finish_proposal().saturating_add(enactment_weight)).into())
 		}
 
 		///Unlocks the locked balance of a voter for a finished proposal.
 		///
 		///This extrinsic can be called by the voter.
 		/// Returns an error if the proposal is still in progress or if the balance
-		/// has already been unlocked.
+		/// has already been unlocked. The cost of a commitment to a secret ballot that was never
+		/// revealed is released, or slashed if `SlashUnrevealedVotes` is set.
 		#[pallet::call_index(8)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::unlock_balance())]
 		pub fn unlock_balance(origin: OriginFor<T>, proposal_id: ProposalId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let proposal: Proposal<T> =
 				Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
 			ensure!(proposal.status != ProposalStatus::InProgress, Error::<T>::ProposalInProgress);
 
-			let mut vote: Vote = <Votes<T>>::try_get(who.clone(), proposal_id)
+			if let Some(commitment) = <Commitments<T>>::get(&who, proposal_id) {
+				return Self::do_remove_commitment(&who, &proposal, commitment)
+			}
+			let vote: VoteOf<T> = <Votes<T>>::try_get(who.clone(), proposal_id)
 				.ok()
 				.ok_or(Error::<T>::VoteNotFound)?;
 			ensure!(vote.locked, Error::<T>::BalanceAlreadyUnocked);
-			vote.locked = false;
-			<Votes<T>>::insert(who.clone(), proposal_id, vote.clone());
 
-			let vote_amount = match vote.vote_decision {
-				VoteDecision::Aye(v) => v,
-				VoteDecision::Nay(v) => v,
-			};
+			Self::do_unlock_balance(who, proposal_id, vote)
+		}
 
-			//unreserve balance corresponding to the vote (amount^2).
-			let amount_to_unreserve: u32 =
-				(vote_amount).checked_pow(2).ok_or(Error::<T>::Overflow)?;
-			T::Currency::unreserve(&who, amount_to_unreserve.into());
+		/// Delegates the votes of the caller on the proposals in `scope` to another registered
+		/// voter, replacing any previous delegation.
+		///
+		/// Whenever the delegate votes, the same vote is cast on behalf of the caller unless they
+		/// already voted on the proposal, paid from the caller's own balance or credits. Votes
+		/// cast on behalf of the caller are not passed on to the caller's own delegators.
+		/// Delegating to an account whose delegations lead back to the caller is rejected.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			scope: DelegationScope,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
+			ensure!(Self::is_registered(&to), Error::<T>::DelegateNotRegistered);
+			Self::ensure_no_delegation_cycle(&who, &to)?;
 
-			Self::deposit_event(Event::BalanceUnlocked { proposal_id, who });
+			Self::remove_delegation(&who);
+			<Delegators<T>>::try_mutate(&to, |delegators| {
+				delegators.try_push(who.clone()).map_err(|_| Error::<T>::TooManyDelegators)
+			})?;
+			<Delegations<T>>::insert(&who, Delegation { delegate: to.clone(), scope });
+
+			Self::deposit_event(Event::Delegated { who, to, scope });
+			Ok(())
+		}
+
+		/// Stops delegating the votes of the caller. Votes already cast on their behalf are kept
+		/// and can be updated or canceled like their own.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<Delegations<T>>::contains_key(&who), Error::<T>::NotDelegating);
+
+			Self::remove_delegation(&who);
+
+			Self::deposit_event(Event::Undelegated { who });
+			Ok(())
+		}
+
+		/// Casts votes signed off-chain on a proposal, so a relayer can submit the votes of many
+		/// voters in a single transaction.
+		///
+		/// Each entry holds the voter, their vote, their next signed-vote nonce and their
+		/// signature of the encoded `SignedVotePayload`, which includes the genesis hash of the
+		/// chain. Entries are applied one by one with the same rules as `vote`. The nonce of the
+		/// voter is increased for every entry with a valid signature and nonce, even if the vote
+		/// fails, so a signed vote can only be submitted once. Entries that fail are reported with
+		/// `SignedVoteRejected` without affecting the rest of the batch.
+		#[pallet::call_index(20)]
+		#[pallet::weight(Pallet::<T>::signed_votes_weight(votes.len() as u32))]
+		pub fn submit_signed_votes(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+			votes: Vec<SignedVoteOf<T>>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(votes.len() as u32 <= T::MaxSignedVotes::get(), Error::<T>::TooManySignedVotes);
+
+			let (mut cast, mut rejected) = (0u32, 0u32);
+			for (who, vote_decision, nonce, signature) in votes {
+				match Self::do_signed_vote(&who, proposal_id, vote_decision, nonce, &signature) {
+					Ok(()) => cast = cast.saturating_add(1),
+					Err(error) => {
+						rejected = rejected.saturating_add(1);
+						Self::deposit_event(Event::SignedVoteRejected { proposal_id, who, error });
+					},
+				}
+			}
+
+			Self::deposit_event(Event::SignedVotesSubmitted { proposal_id, cast, rejected });
+			Ok(())
+		}
+
+		/// Commits a hidden vote to a secret ballot during its commit phase.
+		///
+		/// `commitment` is the hash of the voter, the proposal, the vote and a secret salt, as
+		/// computed by `vote_commitment`. The cost of the most expensive vote the proposal
+		/// accepts is taken from the voter until the vote is revealed, so the commitment does not
+		/// leak the amount of points. Commitments cannot be changed.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::commit_vote())]
+		pub fn commit_vote(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+			commitment: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_registered(&who), Error::<T>::VoterIsNotRegistered);
+
+			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.secret, Error::<T>::NotSecretBallot);
+			ensure!(
+				proposal.status == ProposalStatus::InProgress,
+				Error::<T>::ProposalAlreadyEnded
+			);
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(current_block_number < proposal.reveal_start(), Error::<T>::CommitPhaseEnded);
+			ensure!(
+				!<Commitments<T>>::contains_key(&who, proposal_id) &&
+					!Self::vote_casted(&who, &proposal_id),
+				Error::<T>::VoteAlreadyCasted
+			);
+
+			let cost = Self::vote_cost(&proposal.max_decision())?;
+			Self::take_vote_cost(&who, cost)?;
+			<LockedVotes<T>>::try_mutate(&who, |locked| {
+				locked.try_push(proposal_id).map_err(|_| Error::<T>::TooManyLockedVotes)
+			})?;
+			<Commitments<T>>::insert(
+				&who,
+				proposal_id,
+				Commitment { hash: commitment, cost, round: Self::current_round() },
+			);
+			Self::update_vote_lock(&who)?;
+			Self::note_eligible_voter(&who);
+
+			Self::deposit_event(Event::VoteCommitted { proposal_id, who });
+			Ok(())
+		}
+
+		/// Reveals the vote committed to a secret ballot during its reveal window, adding it to
+		/// the tally.
+		///
+		/// The vote and salt must match the commitment, and the vote must be valid for the
+		/// proposal. The difference between the cost taken at commit time and the cost of the
+		/// revealed vote is given back. Registration is only checked at commit time, so voters
+		/// suspended or expired since then can still reveal instead of losing the commitment.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::reveal_vote())]
+		pub fn reveal_vote(
+			origin: OriginFor<T>,
+			proposal_id: ProposalId,
+			vote_decision: VoteDecisionOf<T>,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
+			let mut proposal =
+				Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+			ensure!(proposal.secret, Error::<T>::NotSecretBallot);
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				proposal.time_period > current_block_number &&
+					proposal.status == ProposalStatus::InProgress,
+				Error::<T>::ProposalAlreadyEnded
+			);
+			ensure!(current_block_number >= proposal.reveal_start(), Error::<T>::NotRevealPhase);
+
+			let commitment =
+				<Commitments<T>>::get(&who, proposal_id).ok_or(Error::<T>::CommitmentNotFound)?;
+			ensure!(
+				commitment.hash == Self::vote_commitment(&who, proposal_id, &vote_decision, &salt),
+				Error::<T>::InvalidReveal
+			);
+			ensure!(proposal.accepts(&vote_decision), Error::<T>::InvalidVoteOptions);
+			ensure!(vote_decision.points() > 0, Error::<T>::InvalidVoteAmount);
+			ensure!(vote_decision.max_points() <= T::VoteLimit::get(), Error::<T>::VoteAmountLimit);
+
+			let cost = Self::vote_cost(&vote_decision)?;
+			Self::refund_vote_cost(&who, commitment.cost.saturating_sub(cost), commitment.round);
+
+			<Commitments<T>>::remove(&who, proposal_id);
+			let vote = Vote {
+				vote_decision: vote_decision.clone(),
+				locked: true,
+				round: commitment.round,
+				paid: cost.saturated_into(),
+				delegated: false,
+			};
+			<Votes<T>>::insert(&who, proposal_id, vote);
+			Self::update_vote_lock(&who)?;
+
+			proposal.add_vote(&vote_decision);
+			proposal.voters = proposal.voters.saturating_add(1);
+			<Proposals<T>>::insert(proposal_id, proposal);
+
+			Self::deposit_event(Event::VoteRevealed { proposal_id, who, vote_decision });
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
 		pub fn is_registered(who: &T::AccountId) -> bool {
-			RegisteredVoters::<T>::contains_key(who)
+			let registered = RegisteredVoters::<T>::get(who).map_or(false, |registration| {
+				!registration.is_expired(<frame_system::Pallet<T>>::block_number())
+			});
+			(registered || T::Eligibility::is_eligible(who)) && !Self::is_suspended(who)
+		}
+		pub fn is_suspended(who: &T::AccountId) -> bool {
+			SuspendedVoters::<T>::contains_key(who)
 		}
 
 		pub fn proposal_exists(proposal_id: ProposalId) -> bool {
@@ -567,6 +1466,927 @@ pub mod pallet {
 			};
 			true
 		}
+		///Adds a voter to the list of registered voters and increases the voter counter.
+		/// 'registrar' is the signed account that registered the voter, if any.
+		pub(crate) fn do_register_voter(
+			who: T::AccountId,
+			registrar: Option<T::AccountId>,
+		) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some(registration) = <RegisteredVoters<T>>::get(&who) {
+				//An expired registration is replaced in its own slot.
+				ensure!(registration.is_expired(now), Error::<T>::AlreadyRegistered);
+				<RegisteredVoters<T>>::insert(who.clone(), Registration::<T>::new(registrar));
+				if <EligibleVoters<T>>::contains_key(&who) {
+					Self::remove_eligible_voter(&who);
+				}
+				Self::deposit_event(Event::VoterRegistered { who });
+				return Ok(())
+			}
+
+			let amount_voters: u32 = <AmountVoters<T>>::try_get().unwrap_or_default();
+			ensure!(amount_voters < T::MaxVoters::get(), Error::<T>::MaxVotersLimitReached);
+
+			//Register voter and increase voter counter
+			<RegisteredVoters<T>>::insert(who.clone(), Registration::<T>::new(registrar));
+			<AmountVoters<T>>::put(amount_voters.saturating_add(1));
+			//Registered voters are already counted in the quorum base.
+			if <EligibleVoters<T>>::contains_key(&who) {
+				Self::remove_eligible_voter(&who);
+			}
+
+			Self::deposit_event(Event::VoterRegistered { who });
+			Ok(())
+		}
+		///Removes an expired registration, freeing its slot and taking it out of the quorum base.
+		/// The rest of the voter's state is cleared as in `remove_voter`, but their votes stay
+		/// counted and can still be unlocked.
+		pub(crate) fn remove_expired_registration(who: &T::AccountId) {
+			Self::clear_registration(who);
+			Self::deposit_event(Event::RegistrationExpired { who: who.clone() });
+		}
+		///Removes the registration of a voter along with their suspension, voice credits and
+		/// delegations from and to them. Returns the number of delegators that were removed.
+		pub(crate) fn clear_registration(who: &T::AccountId) -> u32 {
+			Self::remove_delegation(who);
+			let delegators = <Delegators<T>>::take(who);
+			for delegator in delegators.iter() {
+				<Delegations<T>>::remove(delegator);
+			}
+
+			<RegisteredVoters<T>>::remove(who);
+			<SuspendedVoters<T>>::remove(who);
+			<VoiceCredits<T>>::remove(who);
+			let amount_voters: u32 = <AmountVoters<T>>::try_get().unwrap_or_default();
+			<AmountVoters<T>>::put(amount_voters.saturating_sub(1));
+			delegators.len() as u32
+		}
+		///Counts a voter that can only vote through `Eligibility` in the quorum base, or moves
+		/// their last vote to the current block if they are already counted. Nothing is counted
+		/// once `MaxVoters` eligible voters are.
+		pub(crate) fn note_eligible_voter(who: &T::AccountId) {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let registered = RegisteredVoters::<T>::get(who)
+				.map_or(false, |registration| !registration.is_expired(now));
+			if registered {
+				return
+			}
+			if !<EligibleVoters<T>>::contains_key(who) {
+				let amount = <AmountEligibleVoters<T>>::get();
+				if amount >= T::MaxVoters::get() {
+					return
+				}
+				<AmountEligibleVoters<T>>::put(amount.saturating_add(1));
+			}
+			<EligibleVoters<T>>::insert(who, now);
+		}
+		///Checks if an eligible voter should no longer be part of the quorum base: their last
+		/// vote is older than `RegistrationPeriod`, they lost their eligibility or they were
+		/// registered.
+		pub(crate) fn eligible_voter_lapsed(
+			who: &T::AccountId,
+			last_vote: T::BlockNumber,
+			now: T::BlockNumber,
+		) -> bool {
+			let expired = T::RegistrationPeriod::get()
+				.map_or(false, |period| now >= last_vote.saturating_add(period));
+			expired || !T::Eligibility::is_eligible(who) || <RegisteredVoters<T>>::contains_key(who)
+		}
+		///Takes an eligible voter out of the quorum base.
+		pub(crate) fn remove_eligible_voter(who: &T::AccountId) {
+			<EligibleVoters<T>>::remove(who);
+			<AmountEligibleVoters<T>>::mutate(|amount| *amount = amount.saturating_sub(1));
+		}
+		///Removes the registration request of an account from the pending queue.
+		pub(crate) fn take_registration_request(
+			who: &T::AccountId,
+		) -> Result<RegistrationRequest<T>, DispatchError> {
+			<PendingRegistrations<T>>::try_mutate(|pending| {
+				let index = pending
+					.iter()
+					.position(|request| request.who == *who)
+					.ok_or(Error::<T>::RegistrationRequestNotFound)?;
+				Ok(pending.remove(index))
+			})
+		}
+		///Stores a new in-progress proposal under the next ProposalId and returns that id.
+		pub(crate) fn create_proposal(
+			proposer: T::AccountId,
+			description: T::Hash,
+			params: ProposalParamsOf<T>,
+			time_period: T::BlockNumber,
+			call: Option<BoundedCallOf<T>>,
+			deposit: BalanceOf<T>,
+		) -> Result<ProposalId, DispatchError> {
+			let mut proposal_id: ProposalId = ProposalCounter::<T>::get().unwrap_or_default();
+			ensure!(proposal_id.checked_add(1).is_some(), Error::<T>::ProposalIdToHigh);
+			proposal_id = proposal_id + 1;
+
+			let new_proposal = Proposal::<T>::new(
+				proposal_id,
+				proposer,
+				description,
+				params,
+				time_period,
+				call,
+				deposit,
+			);
+
+			Self::schedule_end(time_period, proposal_id)?;
+			<Proposals<T>>::insert(proposal_id, new_proposal);
+			<ProposalCounter<T>>::put(proposal_id);
+			Ok(proposal_id)
+		}
+		///Bounds the title, summary, URI and options of a new proposal to the pallet limits.
+		pub(crate) fn bound_params(
+			params: ProposalParams,
+		) -> Result<ProposalParamsOf<T>, DispatchError> {
+			let ProposalParams { title, summary, uri, options, threshold, secret } = params;
+			Ok(ProposalParams {
+				title: title.try_into().map_err(|_| Error::<T>::TitleTooLong)?,
+				summary: summary.try_into().map_err(|_| Error::<T>::SummaryTooLong)?,
+				uri: uri.try_into().map_err(|_| Error::<T>::UriTooLong)?,
+				options: Self::bound_options(options)?,
+				threshold,
+				secret,
+			})
+		}
+		///Bounds the option names of a new proposal. Proposals have either no options or at
+		/// least two.
+		pub(crate) fn bound_options(options: Vec<Vec<u8>>) -> Result<OptionsOf<T>, DispatchError> {
+			ensure!(options.len() != 1, Error::<T>::TooFewOptions);
+			ensure!(options.len() as u32 <= T::MaxOptions::get(), Error::<T>::TooManyOptions);
+			let options = options
+				.into_iter()
+				.map(|option| option.try_into().map_err(|_| Error::<T>::OptionTooLong))
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(BoundedVec::truncate_from(options))
+		}
+		///Marks a vote as unlocked and unreserves its balance, or lowers the lock backing the
+		/// votes of the voter. Voice credits are not refunded,
+		/// as they are spent once the proposal ends. Nothing is written if it fails.
+		pub(crate) fn do_unlock_balance(
+			who: T::AccountId,
+			proposal_id: ProposalId,
+			mut vote: VoteOf<T>,
+		) -> DispatchResult {
+			//unreserve balance corresponding to the cost of the vote.
+			let amount_to_unreserve = Self::vote_cost(&vote.vote_decision)?;
+
+			vote.locked = false;
+			<Votes<T>>::insert(who.clone(), proposal_id, vote);
+			Self::remove_locked_vote(&who, proposal_id);
+			Self::release_vote_cost(&who, amount_to_unreserve);
+			Self::update_vote_lock(&who)?;
+
+			Self::deposit_event(Event::BalanceUnlocked { proposal_id, who });
+			Ok(())
+		}
+		///Finalizes proposals that ended, unlocks the votes of finished proposals, removes
+		/// expired registrations and takes lapsed eligible voters out of the quorum base, starting
+		/// from NextSweep and using at most 'limit' weight. Stops after a full pass over
+		/// proposals, votes, registrations and eligible voters. Proposals up to SweptProposals
+		/// are skipped. Returns the weight used.
+		pub(crate) fn sweep(now: T::BlockNumber, limit: Weight) -> Weight {
+			let db = T::DbWeight::get();
+			//NextSweep and SweptProposals are read and written once.
+			let mut used = db.reads_writes(2, 2);
+			if used.any_gt(limit) {
+				return Weight::zero()
+			}
+
+			let finish_weight =
+				T::WeightInfo::finish_proposal().saturating_add(T::MaxEnactmentWeight::get());
+			let unlock_weight = T::WeightInfo::unlock_balance();
+			let prune_weight = T::WeightInfo::prune_registration(T::MaxDelegators::get());
+			let prune_eligible_weight = T::WeightInfo::prune_eligible_voter();
+			//Checking one more proposal, the cursor storage being already accounted for.
+			let check_weight = T::WeightInfo::on_idle(1).saturating_sub(T::WeightInfo::on_idle(0));
+			let mut swept = <SweptProposals<T>>::get();
+			let mut cursor = match <NextSweep<T>>::get() {
+				SweepCursor::Proposals(proposal_id) =>
+					SweepCursor::Proposals(proposal_id.max(swept.saturating_add(1))),
+				cursor => cursor,
+			};
+			loop {
+				match cursor.clone() {
+					SweepCursor::Proposals(proposal_id) => {
+						if used.saturating_add(check_weight).any_gt(limit) {
+							break
+						}
+						used = used.saturating_add(check_weight);
+						if proposal_id > Self::get_proposal_counter() {
+							cursor = SweepCursor::Votes(None);
+							continue
+						}
+						//Closed proposals only cost the check above.
+						let running = match Self::get_proposal(&proposal_id) {
+							Some(proposal) if proposal.status == ProposalStatus::InProgress =>
+								if proposal.time_period >= now {
+									true
+								} else {
+									if used.saturating_add(finish_weight).any_gt(limit) {
+										break
+									}
+									let enactment_weight = Self::do_finish_proposal(proposal);
+									used = used
+										.saturating_add(T::WeightInfo::finish_proposal())
+										.saturating_add(enactment_weight);
+									false
+								},
+							_ => false,
+						};
+						//The mark only moves past proposals once every earlier one is closed.
+						if !running && proposal_id == swept.saturating_add(1) {
+							swept = proposal_id;
+						}
+						cursor = SweepCursor::Proposals(proposal_id.saturating_add(1));
+					},
+					SweepCursor::Votes(last) => {
+						if used.saturating_add(db.reads(1)).any_gt(limit) {
+							break
+						}
+						used = used.saturating_add(db.reads(1));
+						let mut voters = match &last {
+							Some(who) =>
+								<LockedVotes<T>>::iter_from(<LockedVotes<T>>::hashed_key_for(who)),
+							None => <LockedVotes<T>>::iter(),
+						};
+						let (who, locked) = match voters.next() {
+							Some(next) => next,
+							None => {
+								cursor = SweepCursor::Registrations(None);
+								continue
+							},
+						};
+
+						let mut out_of_weight = false;
+						for proposal_id in locked {
+							if used
+								.saturating_add(db.reads(2))
+								.saturating_add(unlock_weight)
+								.any_gt(limit)
+							{
+								out_of_weight = true;
+								break
+							}
+							used = used.saturating_add(db.reads(2));
+							let proposal = match Self::get_proposal(&proposal_id) {
+								Some(p) if p.status != ProposalStatus::InProgress => p,
+								_ => continue,
+							};
+							if let Some(commitment) = <Commitments<T>>::get(&who, proposal_id) {
+								if Self::do_remove_commitment(&who, &proposal, commitment).is_ok() {
+									used = used.saturating_add(unlock_weight);
+								}
+								continue
+							}
+							let vote =
+								<Votes<T>>::get(&who, proposal_id).filter(|vote| vote.locked);
+							if let Some(vote) = vote {
+								if Self::do_unlock_balance(who.clone(), proposal_id, vote).is_ok() {
+									used = used.saturating_add(unlock_weight);
+								}
+							}
+						}
+						//Voters left halfway are checked again in the next block.
+						if out_of_weight {
+							break
+						}
+						cursor = SweepCursor::Votes(Some(who));
+					},
+					SweepCursor::Registrations(last) => {
+						if used
+							.saturating_add(db.reads(1))
+							.saturating_add(prune_weight)
+							.any_gt(limit)
+						{
+							break
+						}
+						used = used.saturating_add(db.reads(1));
+						let mut voters = match &last {
+							Some(who) => <RegisteredVoters<T>>::iter_from(
+								<RegisteredVoters<T>>::hashed_key_for(who),
+							),
+							None => <RegisteredVoters<T>>::iter(),
+						};
+						let (who, registration) = match voters.next() {
+							Some(next) => next,
+							None => {
+								cursor = SweepCursor::EligibleVoters(None);
+								continue
+							},
+						};
+						if registration.is_expired(now) {
+							Self::remove_expired_registration(&who);
+							used = used.saturating_add(prune_weight);
+						}
+						cursor = SweepCursor::Registrations(Some(who));
+					},
+					SweepCursor::EligibleVoters(last) => {
+						//Checking an eligible voter costs as much as removing them.
+						if used.saturating_add(prune_eligible_weight).any_gt(limit) {
+							break
+						}
+						used = used.saturating_add(prune_eligible_weight);
+						let mut voters = match &last {
+							Some(who) => <EligibleVoters<T>>::iter_from(
+								<EligibleVoters<T>>::hashed_key_for(who),
+							),
+							None => <EligibleVoters<T>>::iter(),
+						};
+						let (who, last_vote) = match voters.next() {
+							Some(next) => next,
+							None => {
+								//Full pass done, start over in the next block.
+								cursor = SweepCursor::Proposals(swept.saturating_add(1));
+								break
+							},
+						};
+						if Self::eligible_voter_lapsed(&who, last_vote, now) {
+							Self::remove_eligible_voter(&who);
+						}
+						cursor = SweepCursor::EligibleVoters(Some(who));
+					},
+				}
+			}
+
+			<NextSweep<T>>::put(cursor);
+			<SweptProposals<T>>::put(swept);
+			used
+		}
+		///Adds a proposal to the queue of proposals ending at 'end'.
+		pub(crate) fn schedule_end(end: T::BlockNumber, proposal_id: ProposalId) -> DispatchResult {
+			<ProposalsEndingAt<T>>::try_mutate(end, |ending| {
+				ending
+					.try_push(proposal_id)
+					.map_err(|_| Error::<T>::TooManyProposalsEnding.into())
+			})
+		}
+		///Removes a proposal from the queue of proposals ending at 'end'.
+		pub(crate) fn unschedule_end(end: T::BlockNumber, proposal_id: ProposalId) {
+			<ProposalsEndingAt<T>>::mutate_exists(end, |ending| {
+				if let Some(ids) = ending {
+					ids.retain(|id| *id != proposal_id);
+					if ids.is_empty() {
+						*ending = None;
+					}
+				}
+			});
+		}
+		///Checks that enough voters and vote points took part in a proposal, based on the
+		/// current amount of registered voters and eligible voters that voted recently.
+		pub fn quorum_met(proposal: &Proposal<T>) -> bool {
+			let amount_voters: u32 = <AmountVoters<T>>::get()
+				.unwrap_or_default()
+				.saturating_add(<AmountEligibleVoters<T>>::get());
+			let required_voters = T::QuorumVoters::get().mul_ceil(amount_voters);
+			let points = proposal.points();
+			proposal.voters >= required_voters && points >= T::QuorumPoints::get()
+		}
+		///Computes the result of a proposal, settles the deposit of the proposer and dispatches
+		/// the call if the proposal passed. Returns the weight consumed by the call.
+		pub(crate) fn do_finish_proposal(mut proposal: Proposal<T>) -> Weight {
+			let proposal_id = proposal.id;
+			let voting_result: ProposalStatus = if !Self::quorum_met(&proposal) {
+				ProposalStatus::QuorumNotMet
+			} else if proposal.is_multi_option() {
+				proposal.winning_option()
+			} else {
+				proposal.threshold.tally(proposal.ayes, proposal.nays)
+			};
+
+			proposal.status = voting_result.clone();
+
+			let turnout = proposal.points();
+			Self::settle_proposal_deposit(&proposal, turnout < T::MinimumTurnout::get());
+			let call = proposal.call.clone();
+			if proposal.is_multi_option() {
+				Self::deposit_event(Event::OptionsTallied {
+					proposal_id,
+					tallies: proposal.tallies.clone(),
+				});
+			}
+			<Proposals<T>>::insert(proposal_id, proposal);
+			Self::deposit_event(Event::ProposalEnded {
+				proposal_id,
+				status: voting_result.clone(),
+			});
+
+			let mut enactment_weight = Weight::zero();
+			if let Some(call) = call {
+				if voting_result == ProposalStatus::Passed {
+					enactment_weight = Self::enact_proposal(proposal_id, &call);
+				}
+				T::Preimages::drop(&call);
+			}
+			enactment_weight
+		}
+		///Dispatches the call of a passed proposal with EnactmentOrigin, if it is one of the
+		/// EnactableCalls, and returns the weight it consumed. The preimage of the call is not
+		/// released.
+		pub(crate) fn enact_proposal(proposal_id: ProposalId, call: &BoundedCallOf<T>) -> Weight {
+			let (result, weight) = match T::Preimages::peek(call) {
+				Ok((call, _)) => {
+					let info = call.get_dispatch_info();
+					if !T::EnactableCalls::contains(&call) {
+						(Err(Error::<T>::CallNotEnactable.into()), Weight::zero())
+					} else if info.weight.any_gt(T::MaxEnactmentWeight::get()) {
+						(Err(Error::<T>::EnactmentWeightTooHigh.into()), Weight::zero())
+					} else {
+						let result = call.dispatch(T::EnactmentOrigin::get());
+						let weight = extract_actual_weight(&result, &info);
+						(result.map(|_| ()).map_err(|e| e.error), weight)
+					}
+				},
+				Err(error) => (Err(error), Weight::zero()),
+			};
+			Self::deposit_event(Event::ProposalEnacted { proposal_id, result });
+			weight
+		}
+		///Returns the deposit of a proposal to the proposer, or slashes it if 'slash' is true.
+		pub(crate) fn settle_proposal_deposit(proposal: &Proposal<T>, slash: bool) {
+			if slash {
+				let (imbalance, _) = T::Currency::slash_reserved_named(
+					&T::ReserveId::get(),
+					&proposal.proposer,
+					proposal.deposit,
+				);
+				T::Slash::on_unbalanced(imbalance);
+				Self::deposit_event(Event::ProposalDepositSlashed {
+					proposal_id: proposal.id,
+					amount: proposal.deposit,
+				});
+			} else {
+				T::Currency::unreserve_named(
+					&T::ReserveId::get(),
+					&proposal.proposer,
+					proposal.deposit,
+				);
+			}
+		}
+		///Checks and casts the vote of a voter, then casts it on behalf of their delegators.
+		pub(crate) fn cast_vote(
+			who: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: VoteDecisionOf<T>,
+		) -> DispatchResult {
+			//Verify sender is part of register voters
+			ensure!(Self::is_registered(who), Error::<T>::VoterIsNotRegistered);
+
+			let proposal = Self::get_proposal(&proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+
+			//Check that propossal is not passed removal_treshold
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				proposal.time_period > current_block_number &&
+					proposal.status == ProposalStatus::InProgress,
+				Error::<T>::ProposalAlreadyEnded
+			);
+			ensure!(!proposal.secret, Error::<T>::SecretBallot);
+
+			ensure!(proposal.accepts(&vote_decision), Error::<T>::InvalidVoteOptions);
+			ensure!(vote_decision.points() > 0, Error::<T>::InvalidVoteAmount);
+			ensure!(vote_decision.max_points() <= T::VoteLimit::get(), Error::<T>::VoteAmountLimit);
+
+			//Verify if voter already casted vote. A vote cast by their delegate is replaced, with
+			// the same removal threshold as updating it.
+			match <Votes<T>>::get(who, proposal_id) {
+				Some(vote) if vote.delegated => {
+					if Self::vote_cost(&vote_decision)? < Self::vote_cost(&vote.vote_decision)? {
+						ensure!(
+							!Self::passed_removal_threshold(&proposal.time_period),
+							Error::<T>::PassedRemovalThreshold
+						);
+					}
+					Self::do_cancel_vote(who, proposal_id, proposal, vote)?;
+				},
+				Some(_) => return Err(Error::<T>::VoteAlreadyCasted.into()),
+				None => (),
+			}
+
+			Self::do_vote(who, proposal_id, &vote_decision, false)?;
+			Self::deposit_event(Event::VoteCasted { proposal_id, who: who.clone() });
+
+			Self::apply_delegated_votes(who, proposal_id, &vote_decision);
+			Ok(())
+		}
+		///Checks the signature and nonce of a vote signed off-chain, then casts it.
+		pub(crate) fn do_signed_vote(
+			who: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: VoteDecisionOf<T>,
+			nonce: u32,
+			signature: &T::OffchainSignature,
+		) -> DispatchResult {
+			let payload = SignedVotePayload {
+				genesis_hash: <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero()),
+				proposal_id,
+				vote_decision,
+				nonce,
+			};
+			ensure!(signature.verify(&payload.encode()[..], who), Error::<T>::InvalidSignature);
+			ensure!(nonce == <SignedVoteNonces<T>>::get(who), Error::<T>::InvalidNonce);
+
+			//The nonce is used up even if the vote fails, so the signature cannot be submitted
+			// again once the vote would succeed.
+			<SignedVoteNonces<T>>::insert(who, nonce.saturating_add(1));
+			with_storage_layer(|| Self::cast_vote(who, proposal_id, payload.vote_decision))
+		}
+		///Weight of `submit_signed_votes` with 'n' votes, each cast on behalf of up to
+		/// `MaxDelegators` delegators.
+		pub(crate) fn signed_votes_weight(n: u32) -> Weight {
+			let delegators =
+				T::WeightInfo::vote(T::MaxDelegators::get()).saturating_sub(T::WeightInfo::vote(0));
+			T::WeightInfo::submit_signed_votes(n)
+				.saturating_add(delegators.saturating_mul(n.into()))
+		}
+		///Casts a vote, taking its cost from the voter and adding it to the tally of the proposal.
+		pub(crate) fn do_vote(
+			who: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: &VoteDecisionOf<T>,
+			delegated: bool,
+		) -> DispatchResult {
+			//Reserve balance corresponding to the cost of the vote.
+			let amount_to_reserve = Self::vote_cost(vote_decision)?;
+			Self::take_vote_cost(who, amount_to_reserve)?;
+
+			<LockedVotes<T>>::try_mutate(who, |locked| {
+				locked.try_push(proposal_id).map_err(|_| Error::<T>::TooManyLockedVotes)
+			})?;
+
+			let vote = Vote {
+				vote_decision: vote_decision.clone(),
+				locked: true,
+				round: Self::current_round(),
+				paid: amount_to_reserve.saturated_into(),
+				delegated,
+			};
+
+			//Insert vote and update proposals
+			<Votes<T>>::insert(who, proposal_id, vote);
+			Self::update_vote_lock(who)?;
+
+			<Proposals<T>>::mutate(proposal_id, |proposal| {
+				if let Some(p) = proposal.as_mut() {
+					p.add_vote(vote_decision);
+					p.voters = p.voters.saturating_add(1);
+				}
+			});
+			Self::note_eligible_voter(who);
+			Ok(())
+		}
+		///Casts the vote of a delegate on behalf of each of their delegators that has not voted
+		/// on the proposal. Delegators that cannot pay for the vote are skipped.
+		pub(crate) fn apply_delegated_votes(
+			delegate: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: &VoteDecisionOf<T>,
+		) {
+			for who in <Delegators<T>>::get(delegate) {
+				let covered = <Delegations<T>>::get(&who)
+					.map_or(false, |delegation| delegation.scope.covers(proposal_id));
+				if !covered || !Self::is_registered(&who) || Self::vote_casted(&who, &proposal_id) {
+					continue
+				}
+				let cast =
+					with_storage_layer(|| Self::do_vote(&who, proposal_id, vote_decision, true));
+				if cast.is_ok() {
+					Self::deposit_event(Event::DelegatedVoteCast {
+						proposal_id,
+						delegate: delegate.clone(),
+						who,
+					});
+				}
+			}
+		}
+		///Updates the votes cast on behalf of the delegators of a voter to their new decision,
+		/// then casts it for the delegators that have not voted yet. A delegator that cannot pay
+		/// for the new vote, or is no longer covered or registered, keeps their previous vote.
+		pub(crate) fn update_delegated_votes(
+			delegate: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: &VoteDecisionOf<T>,
+		) {
+			for who in <Delegators<T>>::get(delegate) {
+				let covered = <Delegations<T>>::get(&who)
+					.map_or(false, |delegation| delegation.scope.covers(proposal_id));
+				let vote = <Votes<T>>::get(&who, proposal_id).filter(|vote| vote.delegated);
+				if let (true, Some(vote), Some(proposal)) =
+					(covered && Self::is_registered(&who), vote, Self::get_proposal(&proposal_id))
+				{
+					let previous = vote.vote_decision.clone();
+					let updated = with_storage_layer(|| {
+						Self::do_update_vote(&who, proposal, vote, vote_decision.clone(), true)
+					});
+					if updated.is_ok() {
+						Self::deposit_event(Event::VoteUpdated {
+							proposal_id,
+							who,
+							previous,
+							new: vote_decision.clone(),
+						});
+					}
+				}
+			}
+			Self::apply_delegated_votes(delegate, proposal_id, vote_decision);
+		}
+		///Cancels the votes cast on behalf of the delegators of a voter on an ongoing proposal.
+		pub(crate) fn remove_delegated_votes(delegate: &T::AccountId, proposal_id: ProposalId) {
+			for who in <Delegators<T>>::get(delegate) {
+				let vote = <Votes<T>>::get(&who, proposal_id).filter(|vote| vote.delegated);
+				if let (Some(vote), Some(proposal)) = (vote, Self::get_proposal(&proposal_id)) {
+					let _ = with_storage_layer(|| {
+						Self::do_cancel_vote(&who, proposal_id, proposal, vote)
+					});
+				}
+			}
+		}
+		///Follows the delegations starting at `to`, failing if they lead back to `who` or are
+		/// longer than `MaxDelegationDepth`.
+		pub(crate) fn ensure_no_delegation_cycle(
+			who: &T::AccountId,
+			to: &T::AccountId,
+		) -> DispatchResult {
+			let mut current = to.clone();
+			for _ in 0..T::MaxDelegationDepth::get() {
+				ensure!(current != *who, Error::<T>::DelegationCycle);
+				match <Delegations<T>>::get(&current) {
+					Some(delegation) => current = delegation.delegate,
+					None => return Ok(()),
+				}
+			}
+			Err(Error::<T>::DelegationTooDeep.into())
+		}
+		///Removes the delegation of an account, if any, from the delegators of its delegate.
+		pub(crate) fn remove_delegation(who: &T::AccountId) {
+			if let Some(delegation) = <Delegations<T>>::take(who) {
+				<Delegators<T>>::mutate_exists(&delegation.delegate, |delegators| {
+					if let Some(accounts) = delegators {
+						accounts.retain(|account| account != who);
+						if accounts.is_empty() {
+							*delegators = None;
+						}
+					}
+				});
+			}
+		}
+		///Removes the vote of a voter from an ongoing proposal, correcting the tally and returning
+		/// the reserved balance or voice credits.
+		pub(crate) fn do_cancel_vote(
+			who: &T::AccountId,
+			proposal_id: ProposalId,
+			mut proposal: Proposal<T>,
+			vote: VoteOf<T>,
+		) -> DispatchResult {
+			proposal.remove_vote(&vote.vote_decision);
+			proposal.voters = proposal.voters.saturating_sub(1);
+
+			<Proposals<T>>::insert(proposal_id, proposal);
+			<Votes<T>>::remove(who, proposal_id);
+			Self::remove_locked_vote(who, proposal_id);
+
+			//unreserve balance corresponding to the cost of the vote.
+			let amount_to_unreserve = Self::vote_cost(&vote.vote_decision)?;
+			Self::refund_vote_cost(
+				who,
+				Self::refundable_cost(&vote, amount_to_unreserve),
+				vote.round,
+			);
+			Self::update_vote_lock(who)?;
+
+			Self::deposit_event(Event::VoteCanceled { proposal_id, who: who.clone() });
+			Ok(())
+		}
+		///Replaces a vote on an ongoing proposal, charging or refunding only the difference in
+		/// cost. The vote cannot get cheaper once the removal threshold passed.
+		pub(crate) fn do_update_vote(
+			who: &T::AccountId,
+			mut proposal: Proposal<T>,
+			current_vote: VoteOf<T>,
+			new_vote_decision: VoteDecisionOf<T>,
+			delegated: bool,
+		) -> Result<VoteOf<T>, DispatchError> {
+			ensure!(proposal.accepts(&new_vote_decision), Error::<T>::InvalidVoteOptions);
+			proposal.remove_vote(&current_vote.vote_decision);
+			proposal.add_vote(&new_vote_decision);
+
+			let current_amount_pow = Self::vote_cost(&current_vote.vote_decision)?;
+			let new_amount_pow = Self::vote_cost(&new_vote_decision)?;
+			if new_amount_pow.cmp(&current_amount_pow) == Ordering::Less {
+				//Check threshold
+				ensure!(
+					!Self::passed_removal_threshold(&proposal.time_period),
+					Error::<T>::PassedRemovalThreshold
+				);
+			}
+
+			ensure!(new_vote_decision.points() != 0, Error::<T>::InvalidUpdateAmount);
+			ensure!(
+				new_vote_decision.max_points() <= T::VoteLimit::get(),
+				Error::<T>::VoteAmountLimit
+			);
+
+			//Modify reserved amount. Only the difference is charged or refunded, and an increase
+			// in a later round moves the vote to that round, which then paid for the increase.
+			let mut round = current_vote.round;
+			let mut paid = current_vote.paid;
+			match new_amount_pow.cmp(&current_amount_pow) {
+				Ordering::Greater => {
+					let extra = new_amount_pow - current_amount_pow;
+					Self::take_vote_cost(who, extra)?;
+					let current_round = Self::current_round();
+					if round != current_round {
+						round = current_round;
+						paid = 0;
+					}
+					paid = paid.saturating_add(extra.saturated_into());
+				},
+				Ordering::Less => {
+					let refund =
+						Self::refundable_cost(&current_vote, current_amount_pow - new_amount_pow);
+					Self::refund_vote_cost(who, refund, round);
+					paid = paid.saturating_sub(refund.saturated_into());
+				},
+				_ => (),
+			};
+
+			let new_vote =
+				Vote { vote_decision: new_vote_decision, locked: true, round, paid, delegated };
+
+			<Votes<T>>::insert(who, proposal.id, new_vote.clone());
+			Self::update_vote_lock(who)?;
+			<Proposals<T>>::insert(proposal.id, proposal);
+			Ok(new_vote)
+		}
+		///Hash a voter commits to a secret ballot, hiding their vote behind a secret salt.
+		pub fn vote_commitment(
+			who: &T::AccountId,
+			proposal_id: ProposalId,
+			vote_decision: &VoteDecisionOf<T>,
+			salt: &[u8; 32],
+		) -> T::Hash {
+			T::Hashing::hash_of(&(who, proposal_id, vote_decision, salt))
+		}
+		///Removes a commitment that was not revealed. Its cost is refunded while the proposal is
+		/// in progress and released if it was canceled. Once the proposal finished, the cost is
+		/// slashed if `SlashUnrevealedVotes` is set and it is reserved, otherwise released.
+		pub(crate) fn do_remove_commitment(
+			who: &T::AccountId,
+			proposal: &Proposal<T>,
+			commitment: CommitmentOf<T>,
+		) -> DispatchResult {
+			let proposal_id = proposal.id;
+			<Commitments<T>>::remove(who, proposal_id);
+			Self::remove_locked_vote(who, proposal_id);
+
+			let mut slashed = false;
+			match proposal.status {
+				ProposalStatus::InProgress =>
+					Self::refund_vote_cost(who, commitment.cost, commitment.round),
+				ProposalStatus::Canceled => Self::release_vote_cost(who, commitment.cost),
+				_ if T::SlashUnrevealedVotes::get() &&
+					T::CollateralMode::get() == CollateralMode::Reserve =>
+				{
+					let (imbalance, _) = T::Currency::slash_reserved_named(
+						&T::ReserveId::get(),
+						who,
+						commitment.cost,
+					);
+					T::Slash::on_unbalanced(imbalance);
+					slashed = true;
+				},
+				_ => Self::release_vote_cost(who, commitment.cost),
+			}
+			Self::update_vote_lock(who)?;
+
+			Self::deposit_event(Event::CommitmentRemoved {
+				proposal_id,
+				who: who.clone(),
+				slashed,
+			});
+			Ok(())
+		}
+		///Cost of a vote according to `VoteCost`, summed over its choices. When votes are paid
+		/// with `Currency` the cost is priced at `CostPerPointSquared`, otherwise it is an amount
+		/// of voice credits.
+		pub fn vote_cost(decision: &VoteDecisionOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+			let cost = decision.cost::<BalanceOf<T>, T::VoteCost>().ok_or(Error::<T>::Overflow)?;
+			match T::CollateralMode::get() {
+				CollateralMode::Reserve | CollateralMode::Lock => Ok(cost
+					.checked_mul(&T::CostPerPointSquared::get())
+					.ok_or(Error::<T>::Overflow)?),
+				CollateralMode::Credits => Ok(cost),
+			}
+		}
+		///Total balance this pallet holds from an account: registration and proposal deposits
+		/// plus the cost of votes paid with `Currency`.
+		pub fn held_balance(who: &T::AccountId) -> BalanceOf<T> {
+			T::Currency::reserved_balance_named(&T::ReserveId::get(), who)
+		}
+		///Current credit round, counted in `CreditRoundLength` blocks since genesis.
+		pub fn current_round() -> u32 {
+			let now = <frame_system::Pallet<T>>::block_number();
+			T::CreditRoundLength::get()
+				.filter(|length| !length.is_zero())
+				.map_or(0, |length| (now / length).saturated_into())
+		}
+		///Voice credits a voter has left in the current round.
+		pub fn credits_of(who: &T::AccountId) -> CreditBalance {
+			let round = Self::current_round();
+			<VoiceCredits<T>>::get(who)
+				.filter(|balance| balance.round == round)
+				.unwrap_or(CreditBalance { round, credits: T::CreditsPerRound::get() })
+		}
+		///Takes the cost of a vote from the voter: reserves it from their balance, or debits it
+		/// from their voice credits.
+		pub(crate) fn take_vote_cost(who: &T::AccountId, cost: BalanceOf<T>) -> DispatchResult {
+			match T::CollateralMode::get() {
+				CollateralMode::Reserve =>
+					T::Currency::reserve_named(&T::ReserveId::get(), who, cost),
+				//The lock is recomputed once the vote is stored.
+				CollateralMode::Lock => Ok(()),
+				CollateralMode::Credits => {
+					let cost: u32 = cost.try_into().map_err(|_| Error::<T>::NotEnoughCredits)?;
+					let mut balance = Self::credits_of(who);
+					balance.credits =
+						balance.credits.checked_sub(cost).ok_or(Error::<T>::NotEnoughCredits)?;
+					<VoiceCredits<T>>::insert(who, balance);
+					Ok(())
+				},
+			}
+		}
+		///Part of 'cost' that goes back to a voter when their vote is reduced or canceled. Voice
+		/// credits are only refunded up to what the round of the vote paid for it.
+		pub(crate) fn refundable_cost(vote: &VoteOf<T>, cost: BalanceOf<T>) -> BalanceOf<T> {
+			match T::CollateralMode::get() {
+				CollateralMode::Credits => cost.min(vote.paid.into()),
+				_ => cost,
+			}
+		}
+		///Gives back the cost of a vote that was reduced or canceled. Voice credits are only
+		/// refunded during the round that paid for them.
+		pub(crate) fn refund_vote_cost(who: &T::AccountId, cost: BalanceOf<T>, round: u32) {
+			match T::CollateralMode::get() {
+				CollateralMode::Reserve => {
+					T::Currency::unreserve_named(&T::ReserveId::get(), who, cost);
+				},
+				CollateralMode::Lock => (),
+				CollateralMode::Credits => {
+					let mut balance = Self::credits_of(who);
+					if balance.round == round {
+						balance.credits = balance.credits.saturating_add(cost.saturated_into());
+						<VoiceCredits<T>>::insert(who, balance);
+					}
+				},
+			}
+		}
+		///Releases the cost of a vote on a finished proposal. Only reserved balance is returned,
+		/// voice credits stay spent.
+		pub(crate) fn release_vote_cost(who: &T::AccountId, cost: BalanceOf<T>) {
+			if T::CollateralMode::get() == CollateralMode::Reserve {
+				T::Currency::unreserve_named(&T::ReserveId::get(), who, cost);
+			}
+		}
+		///Sets the lock of a voter to the largest cost among their votes and commitments that
+		/// still hold collateral, when votes are backed by a lock. The lock only blocks transfers,
+		/// so the voter can still pay fees and deposits from it. Raising the lock fails if the
+		/// voter's free balance cannot cover it, lowering it never fails.
+		pub(crate) fn update_vote_lock(who: &T::AccountId) -> DispatchResult {
+			if T::CollateralMode::get() != CollateralMode::Lock {
+				return Ok(())
+			}
+			let lock = <LockedVotes<T>>::get(who)
+				.into_iter()
+				.filter_map(|proposal_id| match <Votes<T>>::get(who, proposal_id) {
+					Some(vote) => Self::vote_cost(&vote.vote_decision).ok(),
+					None => <Commitments<T>>::get(who, proposal_id).map(|c| c.cost),
+				})
+				.max()
+				.unwrap_or_else(Zero::zero);
+			if lock > <VoteLocks<T>>::get(who) {
+				ensure!(lock <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+			}
+			if lock.is_zero() {
+				T::Currency::remove_lock(T::LockId::get(), who);
+				<VoteLocks<T>>::remove(who);
+			} else {
+				T::Currency::set_lock(T::LockId::get(), who, lock, WithdrawReasons::TRANSFER);
+				<VoteLocks<T>>::insert(who, lock);
+			}
+			Ok(())
+		}
+		pub(crate) fn remove_locked_vote(who: &T::AccountId, proposal_id: ProposalId) {
+			<LockedVotes<T>>::mutate_exists(who, |locked| {
+				if let Some(ids) = locked {
+					ids.retain(|id| *id != proposal_id);
+					if ids.is_empty() {
+						*locked = None;
+					}
+				}
+			});
+		}
 		pub fn passed_removal_threshold(end_time_period: &T::BlockNumber) -> bool {
 			let current_block_number = <frame_system::Pallet<T>>::block_number();
 
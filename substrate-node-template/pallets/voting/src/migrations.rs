@@ -0,0 +1,166 @@
+//! Storage migrations for the voting pallet.
+
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_runtime::traits::Zero,
+	traits::{Get, NamedReservableCurrency, OnRuntimeUpgrade, ReservableCurrency, StorageVersion},
+	weights::Weight,
+};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	marker::PhantomData,
+};
+
+use crate::{
+	BalanceOf, CollateralMode, Config, LockedVotes, Pallet, Proposal, ProposalId, ProposalParamsOf,
+	ProposalStatus, Proposals, RegisteredVoters, Registration, Vote, VoteDecision, Votes,
+};
+
+///Migration from the first release of the pallet, which had no storage version, to v1.
+pub mod v1 {
+	use super::*;
+
+	///Storage layouts of the first release of the pallet.
+	pub mod v0 {
+		use super::*;
+
+		#[derive(Encode, Decode)]
+		pub struct Proposal<AccountId, Hash, BlockNumber> {
+			pub id: ProposalId,
+			pub proposer: AccountId,
+			pub text: Hash,
+			pub time_period: BlockNumber,
+			///The variants of the first release are a prefix of the current ones.
+			pub status: ProposalStatus,
+			pub ayes: u32,
+			pub nays: u32,
+		}
+
+		#[derive(Encode, Decode)]
+		pub struct Vote {
+			pub vote_decision: VoteDecision,
+			pub locked: bool,
+		}
+
+		#[derive(Encode, Decode)]
+		pub enum VoteDecision {
+			Aye(u32),
+			Nay(u32),
+		}
+	}
+
+	///Migrates voters, proposals and votes to the v1 layout.
+	///
+	/// - Registered voters get a registration with no registrar, starting at the current block.
+	/// - Proposals get empty metadata, no options or call, a simple majority threshold and no
+	///   deposit. The voter count is rebuilt from the votes, and proposals still in progress are
+	///   queued to end automatically. Proposals whose end already passed, or whose end block is
+	///   full, are left to `finish_proposal` and the sweeper.
+	/// - Votes are marked as cast by the voter in the current credit round without credits paid for
+	///   them, and votes still holding balance are tracked in `LockedVotes`, up to `MaxLockedVotes`
+	///   per voter.
+	/// - The first release reserved the square of the points of each vote without a name. That
+	///   reserve is released, and the vote is backed again as `CollateralMode` requires: by a named
+	///   reserve of its current cost, or of the released amount if the voter cannot cover it, by
+	///   the voter's lock, or not at all when votes are paid with voice credits. The first release
+	///   took no deposits, so there are none to move.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+			let mut reads: u64 = 1;
+			let mut writes: u64 = 1;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let round = Pallet::<T>::current_round();
+
+			<RegisteredVoters<T>>::translate::<(), _>(|_, ()| {
+				reads += 1;
+				writes += 1;
+				Some(Registration::new(None))
+			});
+
+			let mut voters = BTreeMap::<ProposalId, u32>::new();
+			let mut locking = BTreeSet::<T::AccountId>::new();
+			<Votes<T>>::translate::<v0::Vote, _>(|who, proposal_id, old| {
+				reads += 1;
+				writes += 1;
+				let count = voters.entry(proposal_id).or_default();
+				*count = count.saturating_add(1);
+				let (vote_decision, points) = match old.vote_decision {
+					v0::VoteDecision::Aye(points) => (VoteDecision::Aye(points), points),
+					v0::VoteDecision::Nay(points) => (VoteDecision::Nay(points), points),
+				};
+				if old.locked {
+					reads += 3;
+					writes += 3;
+					let _ = <LockedVotes<T>>::try_mutate(&who, |ids| ids.try_push(proposal_id));
+					let old_cost: BalanceOf<T> = points.saturating_pow(2).into();
+					T::Currency::unreserve(&who, old_cost);
+					match T::CollateralMode::get() {
+						CollateralMode::Reserve => {
+							let reserve_id = T::ReserveId::get();
+							let cost = Pallet::<T>::vote_cost(&vote_decision).unwrap_or(old_cost);
+							if T::Currency::reserve_named(&reserve_id, &who, cost).is_err() {
+								let _ = T::Currency::reserve_named(&reserve_id, &who, old_cost);
+							}
+						},
+						CollateralMode::Lock => {
+							locking.insert(who.clone());
+						},
+						CollateralMode::Credits => (),
+					}
+				}
+				//No voice credits were paid for votes of the first release.
+				Some(Vote { vote_decision, locked: old.locked, round, paid: 0, delegated: false })
+			});
+
+			//Votes must all be migrated before the lock of their voter is computed.
+			for who in locking {
+				reads += 2;
+				writes += 2;
+				let _ = Pallet::<T>::update_vote_lock(&who);
+			}
+
+			<Proposals<T>>::translate::<v0::Proposal<T::AccountId, T::Hash, T::BlockNumber>, _>(
+				|proposal_id, old| {
+					reads += 1;
+					writes += 1;
+					if old.status == ProposalStatus::InProgress && old.time_period > now {
+						reads += 1;
+						writes += 1;
+						let _ = Pallet::<T>::schedule_end(old.time_period, proposal_id);
+					}
+					let mut proposal = Proposal::<T>::new(
+						old.id,
+						old.proposer,
+						old.text,
+						ProposalParamsOf::<T>::default(),
+						old.time_period,
+						None,
+						Zero::zero(),
+					);
+					proposal.status = old.status;
+					proposal.ayes = old.ayes;
+					proposal.nays = old.nays;
+					proposal.voters = voters.get(&proposal_id).copied().unwrap_or_default();
+					Some(proposal)
+				},
+			);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: sp_std::vec::Vec<u8>) -> Result<(), &'static str> {
+			frame_support::ensure!(
+				StorageVersion::get::<Pallet<T>>() == 1,
+				"voting storage version was not updated"
+			);
+			Ok(())
+		}
+	}
+}
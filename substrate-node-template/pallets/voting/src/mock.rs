@@ -1,12 +1,16 @@
 use crate as pallet_voting;
+use crate::{CollateralMode, Linear, Quadratic, VoteCost};
 use frame_support::{
-	parameter_types,
-	traits::{ConstU16, ConstU64},
+	ord_parameter_types, parameter_types,
+	traits::{ConstU128, ConstU16, ConstU32, ConstU64, Contains, EitherOfDiverse, GenesisBuild},
+	weights::{RuntimeDbWeight, Weight},
 };
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -22,6 +26,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system,
 		Balances: pallet_balances,
+		Preimage: pallet_preimage,
 		Voting: pallet_voting,
 	}
 );
@@ -30,7 +35,7 @@ impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = DbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Index = u64;
@@ -54,11 +59,42 @@ impl frame_system::Config for Test {
 }
 
 parameter_types! {
+	pub static DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 0, write: 0 };
 	pub const ExistentialDeposit: u64 = 1;
 	pub const MaxLocks: u32 = 10;
 	pub static MaxVoters: u32 = 100;
 	pub static VoteRemovalThreshold: u32 = 20;
+	pub static RevealPeriod: u64 = 3;
+	pub static SlashUnrevealedVotes: bool = false;
 	pub const VoteLimit: u32 = 7;
+	pub const MaxLockedVotes: u32 = 5;
+	pub const MaxDelegators: u32 = 3;
+	pub const MaxDelegationDepth: u32 = 3;
+	pub const MaxSignedVotes: u32 = 4;
+	pub const MaxProposalsEndingPerBlock: u32 = 10;
+	pub const MaxTitleLength: u32 = 16;
+	pub const MaxSummaryLength: u32 = 64;
+	pub const MaxUriLength: u32 = 32;
+	pub const MaxOptions: u32 = 4;
+	pub const MaxOptionLength: u32 = 16;
+	pub const RegistrationDeposit: Balance = 10;
+	pub const MaxPendingRegistrations: u32 = 2;
+	pub static EligibleAccounts: Vec<u64> = vec![];
+	pub static RegistrationPeriod: Option<u64> = None;
+	pub static ProposalDeposit: Balance = 0;
+	pub static SlashCanceledProposals: bool = false;
+	pub static MinimumTurnout: u32 = 0;
+	pub static QuorumVoters: Perbill = Perbill::from_percent(0);
+	pub static QuorumPoints: u32 = 0;
+	pub static LinearCost: bool = false;
+	pub static CostPerPointSquared: Balance = 1;
+	pub const VotingReserveId: [u8; 8] = *b"py/votng";
+	pub const VotingLockId: [u8; 8] = *b"py/votng";
+	pub static VoteCollateral: CollateralMode = CollateralMode::Reserve;
+	pub static CreditsPerRound: u32 = 20;
+	pub static CreditRoundLength: Option<u64> = None;
+	pub static MaxEnactmentWeight: Weight = Weight::from_ref_time(1_000_000_000);
+	pub EnactmentOrigin: RuntimeOrigin = frame_system::RawOrigin::Root.into();
 }
 
 impl pallet_balances::Config for Test {
@@ -69,19 +105,134 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = [u8; 8];
 }
 
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type BaseDeposit = ConstU128<0>;
+	type ByteDeposit = ConstU128<0>;
+}
+
+// Accounts in `EligibleAccounts` can vote without being registered.
+pub struct TestEligibility;
+impl pallet_voting::VoterEligibility<u64> for TestEligibility {
+	fn is_eligible(who: &u64) -> bool {
+		EligibleAccounts::get().contains(who)
+	}
+}
+
+// Super-majorities must require at least half of the points.
+pub struct TestThresholds;
+impl Contains<pallet_voting::ApprovalThreshold> for TestThresholds {
+	fn contains(threshold: &pallet_voting::ApprovalThreshold) -> bool {
+		match threshold {
+			pallet_voting::ApprovalThreshold::SuperMajority(share) =>
+				*share >= Perbill::from_percent(50),
+			_ => true,
+		}
+	}
+}
+
+// Proposals can dispatch any call but a runtime upgrade.
+pub struct TestEnactableCalls;
+impl Contains<RuntimeCall> for TestEnactableCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::System(frame_system::Call::set_code { .. }))
+	}
+}
+
+// Quadratic, unless `LinearCost` is set.
+pub struct TestVoteCost;
+impl VoteCost<Balance> for TestVoteCost {
+	fn cost(points: u32) -> Option<Balance> {
+		if LinearCost::get() {
+			<Linear as VoteCost<Balance>>::cost(points)
+		} else {
+			<Quadratic as VoteCost<Balance>>::cost(points)
+		}
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<UintAuthorityId, TestSignature> for TestBenchmarkHelper {
+	fn signer(index: u32) -> UintAuthorityId {
+		UintAuthorityId(1_000 + index as u64)
+	}
+
+	fn sign(signer: &UintAuthorityId, message: &[u8]) -> TestSignature {
+		TestSignature(signer.0, message.to_vec())
+	}
+}
+
+ord_parameter_types! {
+	pub const Registrar: u64 = 42;
+}
+
 impl pallet_voting::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type ReserveId = VotingReserveId;
+	type LockId = VotingLockId;
+	type RegistrarOrigin = EitherOfDiverse<EnsureRoot<u64>, EnsureSignedBy<Registrar, u64>>;
+	type RemovalOrigin = EnsureRoot<u64>;
+	type RegistrationDeposit = RegistrationDeposit;
+	type MaxPendingRegistrations = MaxPendingRegistrations;
+	type ProposalDeposit = ProposalDeposit;
+	type SlashCanceledProposals = SlashCanceledProposals;
+	type MinimumTurnout = MinimumTurnout;
+	type QuorumVoters = QuorumVoters;
+	type QuorumPoints = QuorumPoints;
+	type ApprovalThresholds = TestThresholds;
+	type Slash = ();
+	type VoteCost = TestVoteCost;
+	type CostPerPointSquared = CostPerPointSquared;
+	type CollateralMode = VoteCollateral;
+	type CreditsPerRound = CreditsPerRound;
+	type CreditRoundLength = CreditRoundLength;
+	type Eligibility = TestEligibility;
+	type RegistrationPeriod = RegistrationPeriod;
 	type MaxVoters = MaxVoters;
 	type VoteRemovalThreshold = VoteRemovalThreshold;
+	type RevealPeriod = RevealPeriod;
+	type SlashUnrevealedVotes = SlashUnrevealedVotes;
 	type VoteLimit = VoteLimit;
+	type MaxLockedVotes = MaxLockedVotes;
+	type MaxDelegators = MaxDelegators;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	type MaxSignedVotes = MaxSignedVotes;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = TestBenchmarkHelper;
+	type MaxProposalsEndingPerBlock = MaxProposalsEndingPerBlock;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxSummaryLength = MaxSummaryLength;
+	type MaxUriLength = MaxUriLength;
+	type MaxOptions = MaxOptions;
+	type MaxOptionLength = MaxOptionLength;
+	type RuntimeCall = RuntimeCall;
+	type Preimages = Preimage;
+	type EnactmentOrigin = EnactmentOrigin;
+	type EnactableCalls = TestEnactableCalls;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
 }
+
+// Build genesis storage with the given pallet_voting genesis configuration.
+pub fn new_test_ext_with(voting: pallet_voting::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	voting.assimilate_storage(&mut storage).unwrap();
+	storage.into()
+}
@@ -1,5 +1,41 @@
-use crate::{mock::*, Error, Event, Proposal, ProposalStatus, VoteDecision};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use crate::{
+	self as pallet_voting, mock::*, ApprovalThreshold, Error, Event, Proposal, ProposalParams,
+	ProposalStatus, VoteDecision,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, ReservableCurrency},
+};
+use sp_runtime::DispatchResult;
+
+// Submits a simple majority proposal with a zero content hash and placeholder metadata.
+fn submit_proposal(proposer: u64, time_period: u64) -> DispatchResult {
+	submit_proposal_with_threshold(proposer, time_period, ApprovalThreshold::SimpleMajority)
+}
+
+fn submit_proposal_with_threshold(
+	proposer: u64,
+	time_period: u64,
+	threshold: ApprovalThreshold,
+) -> DispatchResult {
+	Voting::make_proposal(
+		RuntimeOrigin::signed(proposer),
+		sp_core::H256::zero(),
+		ProposalParams { threshold, ..params() },
+		time_period,
+		None,
+	)
+}
+
+// Placeholder metadata of an open aye/nay proposal with a simple majority threshold.
+fn params() -> ProposalParams {
+	ProposalParams {
+		title: b"Title".to_vec(),
+		summary: b"Summary".to_vec(),
+		uri: b"ipfs://proposal".to_vec(),
+		..Default::default()
+	}
+}
 
 mod register_voter {
 	use super::*;
@@ -33,6 +69,21 @@ mod register_voter {
 		});
 	}
 
+	#[test]
+	fn registrar_origin_can_register() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::signed(Registrar::get()), 2));
+			assert!(Voting::is_registered(&2));
+
+			//Registrar is not allowed to remove voters
+			assert_noop!(
+				Voting::remove_voter(RuntimeOrigin::signed(Registrar::get()), 2),
+				sp_runtime::DispatchError::BadOrigin
+			);
+		});
+	}
+
 	#[test]
 	fn reached_max_voters() {
 		new_test_ext().execute_with(|| {
@@ -46,400 +97,3027 @@ mod register_voter {
 	}
 }
 
-mod create_proposal {
+mod remove_voter {
 	use super::*;
 
 	#[test]
-	fn make_proposal() {
+	fn voter_removed() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(82);
-			let initial_proposal_id = Voting::get_proposal_counter();
-			let new_proposal_id = initial_proposal_id + 1;
+			System::set_block_number(1);
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
-			assert!(Voting::proposal_exists(new_proposal_id));
-
-			System::assert_has_event(
-				Event::ProposalSubmitted { proposal_id: new_proposal_id, who: 1 }.into(),
-			);
+			assert_ok!(Voting::remove_voter(RuntimeOrigin::root(), 2));
+			assert!(!Voting::is_registered(&2));
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(1));
+			System::assert_has_event(Event::VoterRemoved { who: 2 }.into());
 
-			assert_eq!(initial_proposal_id + 1, Voting::get_proposal_counter());
+			//The freed slot can be used again
+			MaxVoters::set(2);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
 		});
 	}
 
 	#[test]
-	fn proposal_time_low() {
+	fn ongoing_votes_are_canceled() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(82);
+			System::set_block_number(1);
+			let initial_balance: u32 = 25;
+			Balances::make_free_balance_be(&2, initial_balance.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(submit_proposal(1, 90));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
 
-			assert_noop!(
-				Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 80),
-				Error::<Test>::TimePeriodToLow
-			);
+			//Removal ignores the removal threshold
+			System::set_block_number(85);
+			assert_ok!(Voting::remove_voter(RuntimeOrigin::root(), 2));
+
+			System::assert_has_event(Event::VoteCanceled { proposal_id, who: 2 }.into());
+			assert!(!Voting::vote_casted(&2, &proposal_id));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().ayes, 0);
+			assert_eq!(Balances::free_balance(&2), initial_balance as u128);
 		});
 	}
 
 	#[test]
-	fn proposer_not_registeredd() {
+	fn finished_votes_can_still_be_unlocked() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(82);
+			System::set_block_number(1);
+			let initial_balance: u32 = 25;
+			Balances::make_free_balance_be(&2, initial_balance.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(submit_proposal(1, 5));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
 
+			assert_ok!(Voting::remove_voter(RuntimeOrigin::root(), 2));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().ayes, 3);
+
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Balances::free_balance(&2), initial_balance as u128);
+		});
+	}
+
+	#[test]
+	fn voter_not_registered() {
+		new_test_ext().execute_with(|| {
 			assert_noop!(
-				Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90),
+				Voting::remove_voter(RuntimeOrigin::root(), 2),
 				Error::<Test>::VoterIsNotRegistered
 			);
+			assert_noop!(
+				Voting::remove_voter(RuntimeOrigin::signed(1), 2),
+				sp_runtime::DispatchError::BadOrigin
+			);
 		});
 	}
 }
 
-mod increase_proposal_time {
+mod suspend_voter {
 	use super::*;
 
 	#[test]
-	fn update_proposal() {
+	fn suspended_voter_cannot_vote() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&2, 25u32.into());
 			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(submit_proposal(1, 90));
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
-			assert_ok!(Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 95));
-
-			System::assert_has_event(Event::ProposalUpdated { proposal_id, end_block: 95 }.into());
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), 2));
+			System::assert_has_event(Event::VoterSuspended { who: 2 }.into());
+			assert!(!Voting::is_registered(&2));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::VoterIsNotRegistered
+			);
+			assert_noop!(
+				Voting::register_voter(RuntimeOrigin::root(), 2),
+				Error::<Test>::AlreadyRegistered
+			);
+			assert_noop!(
+				Voting::suspend_voter(RuntimeOrigin::root(), 2),
+				Error::<Test>::AlreadySuspended
+			);
 
-			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
-			assert_eq!(updated_proposal.time_period, 95)
+			assert_ok!(Voting::reinstate_voter(RuntimeOrigin::root(), 2));
+			System::assert_has_event(Event::VoterReinstated { who: 2 }.into());
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
 		});
 	}
 
 	#[test]
-	fn proposer_not_registered() {
+	fn votes_are_frozen_until_proposal_ends() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
+			System::set_block_number(1);
+			let initial_balance: u32 = 25;
+			Balances::make_free_balance_be(&2, initial_balance.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(submit_proposal(1, 50));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Nay(2)));
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), 2));
 
 			assert_noop!(
-				Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90),
+				Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id),
+				Error::<Test>::VoterSuspended
+			);
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Nay(1)),
 				Error::<Test>::VoterIsNotRegistered
 			);
+
+			System::set_block_number(51);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Rejected }.into(),
+			);
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Balances::free_balance(&2), initial_balance as u128);
 		});
 	}
 
 	#[test]
-	fn update_proposal_invalid() {
+	fn reinstate_requires_suspension() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_noop!(
+				Voting::reinstate_voter(RuntimeOrigin::root(), 2),
+				Error::<Test>::NotSuspended
+			);
+		});
+	}
+}
+
+mod registration_expiry {
+	use super::*;
+	use frame_support::{traits::Hooks, weights::Weight};
+
+	#[test]
+	fn registration_stores_metadata() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(3);
+			RegistrationPeriod::set(Some(10));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::signed(Registrar::get()), 2));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
+
+			let registration = pallet_voting::RegisteredVoters::<Test>::get(2).unwrap();
+			assert_eq!(registration.registrar, Some(Registrar::get()));
+			assert_eq!(registration.registered_at, 3);
+			assert_eq!(registration.expires_at, Some(13));
+			assert_eq!(pallet_voting::RegisteredVoters::<Test>::get(3).unwrap().registrar, None);
+		});
+	}
+
+	#[test]
+	fn expired_voter_is_not_registered() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			RegistrationPeriod::set(Some(10));
+			Balances::make_free_balance_be(&2, 25u32.into());
 			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
+			assert_ok!(Voting::renew_registration(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 50));
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
+			System::set_block_number(11);
+			assert!(!Voting::is_registered(&2));
 			assert_noop!(
-				Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 75),
-				Error::<Test>::TimePeriodToLow
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::VoterIsNotRegistered
+			);
+
+			//Renewing an expired registration starts a new period from now
+			assert_ok!(Voting::renew_registration(RuntimeOrigin::root(), 2));
+			System::assert_has_event(
+				Event::RegistrationRenewed { who: 2, expires_at: Some(21) }.into(),
 			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
+
+			//Registering an expired voter again reuses its slot
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(3));
+			assert!(Voting::is_registered(&3));
 		});
 	}
 
 	#[test]
-	fn invalid_proposer_update() {
+	fn expired_registrations_pruned() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
-			let proposal_id = Voting::get_proposal_counter() + 1;
+			System::set_block_number(1);
+			RegistrationPeriod::set(Some(10));
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(Voting::renew_registration(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::issue_credits(RuntimeOrigin::root(), 2, 5));
+			assert_ok!(Voting::delegate(
+				RuntimeOrigin::signed(1),
+				2,
+				pallet_voting::DelegationScope::All
+			));
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), 2));
+
+			System::set_block_number(11);
+			Voting::on_idle(11, Weight::MAX);
+			System::assert_has_event(Event::RegistrationExpired { who: 2 }.into());
+			assert!(!pallet_voting::RegisteredVoters::<Test>::contains_key(2));
+			assert!(pallet_voting::RegisteredVoters::<Test>::contains_key(1));
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(1));
+
+			//The rest of the voter's state is cleared as when removing them
+			assert!(!pallet_voting::SuspendedVoters::<Test>::contains_key(2));
+			assert!(!pallet_voting::VoiceCredits::<Test>::contains_key(2));
+			assert!(!pallet_voting::Delegations::<Test>::contains_key(1));
+			assert!(pallet_voting::Delegators::<Test>::get(2).is_empty());
+		});
+	}
+
+	#[test]
+	fn expired_voter_can_request_registration() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			RegistrationPeriod::set(Some(10));
+			Balances::make_free_balance_be(&2, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_noop!(
+				Voting::request_registration(RuntimeOrigin::signed(2)),
+				Error::<Test>::AlreadyRegistered
+			);
+
+			System::set_block_number(11);
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(2)));
+			assert_ok!(Voting::approve_registration(RuntimeOrigin::root(), 2));
+			assert!(Voting::is_registered(&2));
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(1));
+		});
+	}
+
+	#[test]
+	fn renewal_extends_current_period() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			RegistrationPeriod::set(Some(10));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+
+			System::set_block_number(5);
+			assert_ok!(Voting::renew_registration(RuntimeOrigin::signed(Registrar::get()), 2));
+			System::assert_has_event(
+				Event::RegistrationRenewed { who: 2, expires_at: Some(21) }.into(),
+			);
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
 			assert_noop!(
-				Voting::increase_proposal_time(RuntimeOrigin::signed(2), proposal_id, 95),
-				Error::<Test>::Unauthorized
+				Voting::renew_registration(RuntimeOrigin::signed(2), 2),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Voting::renew_registration(RuntimeOrigin::root(), 3),
+				Error::<Test>::VoterIsNotRegistered
 			);
 		});
 	}
 }
 
-mod cancel_proposal {
+mod registration_requests {
 	use super::*;
 
 	#[test]
-	fn proposal_canceled() {
+	fn request_is_approved() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&2, 25u32.into());
+
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(2)));
+			System::assert_has_event(Event::RegistrationRequested { who: 2, deposit: 10 }.into());
+			assert_eq!(Balances::reserved_balance(&2), 10);
+			assert!(!Voting::is_registered(&2));
+			assert_noop!(
+				Voting::request_registration(RuntimeOrigin::signed(2)),
+				Error::<Test>::RegistrationAlreadyRequested
+			);
+
+			assert_ok!(Voting::approve_registration(RuntimeOrigin::signed(Registrar::get()), 2));
+			System::assert_has_event(Event::RegistrationApproved { who: 2 }.into());
+			assert!(Voting::is_registered(&2));
+			assert_eq!(Balances::free_balance(&2), 25);
+			assert!(pallet_voting::PendingRegistrations::<Test>::get().is_empty());
+
+			assert_noop!(
+				Voting::request_registration(RuntimeOrigin::signed(2)),
+				Error::<Test>::AlreadyRegistered
+			);
+		});
+	}
+
+	#[test]
+	fn request_is_rejected() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&2, 25u32.into());
+			Balances::make_free_balance_be(&3, 25u32.into());
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(2)));
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(3)));
+
+			//Returned deposit
+			assert_ok!(Voting::reject_registration(RuntimeOrigin::root(), 2, false));
+			System::assert_has_event(Event::RegistrationRejected { who: 2, slashed: false }.into());
+			assert_eq!(Balances::free_balance(&2), 25);
+
+			//Slashed deposit
+			let issuance = Balances::total_issuance();
+			assert_ok!(Voting::reject_registration(RuntimeOrigin::root(), 3, true));
+			System::assert_has_event(Event::RegistrationRejected { who: 3, slashed: true }.into());
+			assert_eq!(Balances::total_balance(&3), 15);
+			assert_eq!(Balances::total_issuance(), issuance - 10);
+
+			assert!(!Voting::is_registered(&2));
+			assert!(!Voting::is_registered(&3));
+			assert_noop!(
+				Voting::approve_registration(RuntimeOrigin::root(), 3),
+				Error::<Test>::RegistrationRequestNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn request_limits() {
+		new_test_ext().execute_with(|| {
+			for who in 2..5 {
+				Balances::make_free_balance_be(&who, 25u32.into());
+			}
+			//Not enough balance for the deposit
+			assert!(Voting::request_registration(RuntimeOrigin::signed(1)).is_err());
+
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(2)));
+			assert_ok!(Voting::request_registration(RuntimeOrigin::signed(3)));
+			assert_noop!(
+				Voting::request_registration(RuntimeOrigin::signed(4)),
+				Error::<Test>::TooManyPendingRegistrations
+			);
+
+			assert_noop!(
+				Voting::approve_registration(RuntimeOrigin::signed(2), 3),
+				sp_runtime::DispatchError::BadOrigin
+			);
+
+			//Approval still respects the voter limit
+			MaxVoters::set(0);
+			assert_noop!(
+				Voting::approve_registration(RuntimeOrigin::root(), 3),
+				Error::<Test>::MaxVotersLimitReached
+			);
+		});
+	}
+}
+
+mod eligibility {
+	use super::*;
+	use frame_support::{traits::Hooks, weights::Weight};
+
+	#[test]
+	fn eligible_account_can_vote_without_registration() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&2, 25u32.into());
 			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 50));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::VoterIsNotRegistered
+			);
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
-			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
-			System::assert_has_event(Event::ProposalCanceled { proposal_id }.into());
+			EligibleAccounts::set(vec![2]);
+			assert!(Voting::is_registered(&2));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
+			//Eligible accounts do not take a registration slot
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(1));
 
-			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
-			assert_eq!(updated_proposal.status, ProposalStatus::Canceled);
+			//Losing eligibility removes the voting rights
+			EligibleAccounts::set(vec![]);
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)),
+				Error::<Test>::VoterIsNotRegistered
+			);
 		});
 	}
 
 	#[test]
-	fn proposal_cant_be_canceled() {
+	fn eligible_voter_leaves_quorum_base() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(30);
+			RegistrationPeriod::set(Some(10));
+			System::set_block_number(1);
+			EligibleAccounts::set(vec![2, 3]);
 			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 50));
+			for who in 2..=3 {
+				Balances::make_free_balance_be(&who, 25u32.into());
+				assert_ok!(Voting::vote(
+					RuntimeOrigin::signed(who),
+					proposal_id,
+					VoteDecision::Aye(1)
+				));
+			}
+			assert_eq!(pallet_voting::AmountEligibleVoters::<Test>::get(), 2);
+
+			//Registered voters are only counted once
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
+			assert!(!pallet_voting::EligibleVoters::<Test>::contains_key(3));
+			assert_eq!(pallet_voting::AmountEligibleVoters::<Test>::get(), 1);
+
+			Voting::on_idle(10, Weight::MAX);
+			assert!(pallet_voting::EligibleVoters::<Test>::contains_key(2));
+
+			//The last vote is older than the registration period
+			Voting::on_idle(11, Weight::MAX);
+			assert!(!pallet_voting::EligibleVoters::<Test>::contains_key(2));
+			assert_eq!(pallet_voting::AmountEligibleVoters::<Test>::get(), 0);
+		});
+	}
 
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
+	#[test]
+	fn eligible_account_can_be_suspended() {
+		new_test_ext().execute_with(|| {
+			EligibleAccounts::set(vec![2]);
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), 2));
+			assert!(!Voting::is_registered(&2));
 
-			System::set_block_number(100);
+			assert_ok!(Voting::reinstate_voter(RuntimeOrigin::root(), 2));
+			assert!(Voting::is_registered(&2));
+		});
+	}
+}
 
-			assert_noop!(
-				Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id),
-				Error::<Test>::TimePeriodToLow
-			);
+mod genesis {
+	use super::*;
+
+	#[test]
+	fn genesis_registers_voters_and_proposals() {
+		new_test_ext_with(pallet_voting::GenesisConfig {
+			voters: vec![1, 2],
+			proposals: vec![(1, sp_core::H256::zero(), 10)],
+		})
+		.execute_with(|| {
+			assert!(Voting::is_registered(&1));
+			assert!(Voting::is_registered(&2));
+			assert_eq!(pallet_voting::AmountVoters::<Test>::get(), Some(2));
+
+			let proposal: Proposal<Test> = Voting::get_proposal(&1).unwrap();
+			assert_eq!(proposal.proposer, 1);
+			assert_eq!(proposal.time_period, 10);
+			assert_eq!(proposal.status, ProposalStatus::InProgress);
+			assert_eq!(Voting::get_proposal_counter(), 1);
 		});
 	}
-}
 
-mod vote {
-	use super::*;
+	#[test]
+	#[should_panic(expected = "Genesis voters exceed the MaxVoters limit")]
+	fn genesis_respects_max_voters() {
+		MaxVoters::set(1);
+		new_test_ext_with(pallet_voting::GenesisConfig { voters: vec![1, 2], proposals: vec![] });
+	}
+
+	#[test]
+	#[should_panic(expected = "Genesis proposer is not a voter")]
+	fn genesis_proposer_must_be_voter() {
+		new_test_ext_with(pallet_voting::GenesisConfig {
+			voters: vec![1],
+			proposals: vec![(2, sp_core::H256::zero(), 10)],
+		});
+	}
+}
+
+mod create_proposal {
+	use super::*;
+
+	#[test]
+	fn make_proposal() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(82);
+			let initial_proposal_id = Voting::get_proposal_counter();
+			let new_proposal_id = initial_proposal_id + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert!(Voting::proposal_exists(new_proposal_id));
+
+			System::assert_has_event(
+				Event::ProposalSubmitted {
+					proposal_id: new_proposal_id,
+					who: 1,
+					title: b"Title".to_vec().try_into().unwrap(),
+				}
+				.into(),
+			);
+			let proposal = Voting::get_proposal(&new_proposal_id).unwrap();
+			assert_eq!(proposal.summary.to_vec(), b"Summary".to_vec());
+			assert_eq!(proposal.uri.to_vec(), b"ipfs://proposal".to_vec());
+
+			assert_eq!(initial_proposal_id + 1, Voting::get_proposal_counter());
+		});
+	}
+
+	#[test]
+	fn proposal_time_low() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(82);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_noop!(submit_proposal(1, 80), Error::<Test>::TimePeriodToLow);
+		});
+	}
+
+	#[test]
+	fn metadata_too_long() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			let make_proposal = |title: usize, summary: usize, uri: usize| {
+				let params = ProposalParams {
+					title: vec![b'a'; title],
+					summary: vec![b'a'; summary],
+					uri: vec![b'a'; uri],
+					..Default::default()
+				};
+				Voting::make_proposal(
+					RuntimeOrigin::signed(1),
+					sp_core::H256::zero(),
+					params,
+					90,
+					None,
+				)
+			};
+			let (title, summary, uri) = (
+				MaxTitleLength::get() as usize,
+				MaxSummaryLength::get() as usize,
+				MaxUriLength::get() as usize,
+			);
+
+			assert_noop!(make_proposal(title + 1, 0, 0), Error::<Test>::TitleTooLong);
+			assert_noop!(make_proposal(0, summary + 1, 0), Error::<Test>::SummaryTooLong);
+			assert_noop!(make_proposal(0, 0, uri + 1), Error::<Test>::UriTooLong);
+			assert_ok!(make_proposal(title, summary, uri));
+		});
+	}
+
+	#[test]
+	fn proposer_not_registeredd() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(82);
+
+			assert_noop!(submit_proposal(1, 90), Error::<Test>::VoterIsNotRegistered);
+		});
+	}
+}
+
+mod proposal_deposit {
+	use super::*;
+
+	fn before_each() -> u32 {
+		ProposalDeposit::set(10);
+		Balances::make_free_balance_be(&1, 50u32.into());
+		Balances::make_free_balance_be(&2, 50u32.into());
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		proposal_id
+	}
+
+	#[test]
+	fn deposit_returned_when_finished() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = before_each();
+			assert_eq!(Balances::reserved_balance(&1), 10);
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().deposit, 10);
+
+			MinimumTurnout::set(2);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Balances::free_balance(&1), 50);
+		});
+	}
+
+	#[test]
+	fn deposit_slashed_below_minimum_turnout() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = before_each();
+
+			MinimumTurnout::set(3);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
+			System::assert_has_event(
+				Event::ProposalDepositSlashed { proposal_id, amount: 10 }.into(),
+			);
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+			);
+			assert_eq!(Balances::total_balance(&1), 40);
+		});
+	}
+
+	#[test]
+	fn deposit_on_cancel() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = before_each();
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_eq!(Balances::free_balance(&1), 50);
+
+			SlashCanceledProposals::set(true);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_proposal(1, 5));
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalDepositSlashed { proposal_id, amount: 10 }.into(),
+			);
+			assert_eq!(Balances::total_balance(&1), 40);
+		});
+	}
+
+	#[test]
+	fn deposit_required() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			ProposalDeposit::set(10);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert!(submit_proposal(1, 5).is_err());
+			assert!(!Voting::proposal_exists(1));
+		});
+	}
+
+	#[test]
+	fn deposits_held_under_reserve_id() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(3)));
+			assert_eq!(Voting::held_balance(&1), 19);
+
+			// Another pallet releasing its own reserves cannot free the voting collateral.
+			assert_ok!(Balances::reserve(&1, 5));
+			assert_eq!(Balances::unreserve(&1, 50), 45);
+			assert_eq!(Balances::reserved_balance(&1), 19);
+			assert_eq!(Voting::held_balance(&1), 19);
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(1), proposal_id));
+			assert_eq!(Voting::held_balance(&1), 0);
+			assert_eq!(Balances::free_balance(&1), 50);
+		});
+	}
+}
+
+mod increase_proposal_time {
+	use super::*;
+
+	#[test]
+	fn update_proposal() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert_ok!(Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 95));
+
+			System::assert_has_event(Event::ProposalUpdated { proposal_id, end_block: 95 }.into());
+
+			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!(updated_proposal.time_period, 95)
+		});
+	}
+
+	#[test]
+	fn proposer_not_registered() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+
+			assert_noop!(submit_proposal(1, 90), Error::<Test>::VoterIsNotRegistered);
+		});
+	}
+
+	#[test]
+	fn update_proposal_invalid() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert_noop!(
+				Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 75),
+				Error::<Test>::TimePeriodToLow
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_proposer_update() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert_noop!(
+				Voting::increase_proposal_time(RuntimeOrigin::signed(2), proposal_id, 95),
+				Error::<Test>::Unauthorized
+			);
+		});
+	}
+
+	#[test]
+	fn closed_proposal_not_rescheduled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_noop!(
+				Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 95),
+				Error::<Test>::ProposalAlreadyEnded
+			);
+			assert!(pallet_voting::ProposalsEndingAt::<Test>::get(95).is_empty());
+		});
+	}
+}
+
+mod cancel_proposal {
+	use super::*;
+
+	#[test]
+	fn proposal_canceled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(Event::ProposalCanceled { proposal_id }.into());
+
+			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!(updated_proposal.status, ProposalStatus::Canceled);
+		});
+	}
+
+	#[test]
+	fn proposal_cant_be_canceled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(30);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_ok!(submit_proposal(1, 90));
+
+			System::set_block_number(100);
+
+			assert_noop!(
+				Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id),
+				Error::<Test>::TimePeriodToLow
+			);
+		});
+	}
+}
+
+mod vote {
+	use super::*;
+
+	#[test]
+	fn cast_valid_votes() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			let initial_balance: u32 = 25;
+			Balances::make_free_balance_be(&1, initial_balance.into());
+			assert_ok!(submit_proposal(1, 90));
+
+			//Vote in favor and verify that the functions excecutes properly and the event is
+			// created
+			let vote_amount: u32 = 2;
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(1),
+				proposal_id,
+				VoteDecision::Aye(vote_amount)
+			));
+			System::assert_has_event(Event::VoteCasted { proposal_id, who: 1 }.into());
+
+			//Check that the reserved amount from the user is (amount of votes^2)
+			let user_balance = Balances::free_balance(&1);
+			assert_eq!(initial_balance, user_balance as u32 + vote_amount.pow(2));
+
+			//Check that the vote is in storage and the proposal updated properly
+			assert!(Voting::vote_casted(&1, &proposal_id));
+			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!(updated_proposal.ayes, vote_amount);
+
+			//Vote nay and verify that the changes are correct in storage
+			Balances::make_free_balance_be(&2, 25u32.into());
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(vote_amount)
+			));
+			System::assert_has_event(Event::VoteCasted { proposal_id, who: 2 }.into());
+			assert!(Voting::vote_casted(&2, &proposal_id));
+			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!(updated_proposal.nays, vote_amount);
+		});
+	}
+
+	#[test]
+	fn voter_not_registered() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 90));
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::VoterIsNotRegistered
+			);
+		});
+	}
+
+	#[test]
+	fn vote_already_casted() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&1, 25u32.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 90));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::VoteAlreadyCasted
+			);
+		});
+	}
+
+	#[test]
+	fn vote_over_limit() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&1, 25u32.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 90));
+
+			let vote_limit: u32 = VoteLimit::get();
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(1),
+					proposal_id,
+					VoteDecision::Aye(vote_limit + 1)
+				),
+				Error::<Test>::VoteAmountLimit
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_proposal() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&1, 25u32.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(2)),
+				Error::<Test>::ProposalNotFound
+			);
+		});
+	}
+
+	#[test]
+	fn ended_proposal() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&1, 25u32.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 10));
+
+			System::set_block_number(20);
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(2)),
+				Error::<Test>::ProposalAlreadyEnded
+			);
+		});
+	}
+
+	#[test]
+	fn invalid_vote_amount() {
+		new_test_ext().execute_with(|| {
+			//Initial setup
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 90));
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(0)),
+				Error::<Test>::InvalidVoteAmount
+			);
+		});
+	}
+}
+
+mod locked_votes {
+	use super::*;
+
+	#[test]
+	fn locked_votes_limit() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&1, 100u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			for _ in 0..MaxLockedVotes::get() {
+				assert_ok!(submit_proposal(1, 5));
+				let proposal_id = Voting::get_proposal_counter();
+				assert_ok!(Voting::vote(
+					RuntimeOrigin::signed(1),
+					proposal_id,
+					VoteDecision::Aye(1)
+				));
+			}
+			assert_ok!(submit_proposal(1, 5));
+			let proposal_id = Voting::get_proposal_counter();
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)),
+				Error::<Test>::TooManyLockedVotes
+			);
+
+			//Unlocking a finished proposal frees a slot
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), 1));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(1), 1));
+			assert_ok!(Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 10));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+		});
+	}
+}
+
+mod finish_proposal {
+	use super::*;
+
+	#[test]
+	fn proposal_passed() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			Balances::make_free_balance_be(&1, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+
+			System::set_block_number(6);
+
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn proposal_rejected() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			Balances::make_free_balance_be(&1, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Nay(1)));
+
+			System::set_block_number(6);
+
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Rejected }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn proposal_tied() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+
+			System::set_block_number(6);
+
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Tied }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn finish_proposal_fails_if_canceled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+
+			assert_noop!(
+				Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id),
+				Error::<Test>::ProposalAlreadyEnded
+			);
+		});
+	}
+
+	#[test]
+	fn finish_proposal_early_rejects() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+
+			assert_noop!(
+				Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id),
+				Error::<Test>::ProposalAlreadyEnded
+			);
+		});
+	}
+}
+
+mod automatic_closing {
+	use super::*;
+	use frame_support::traits::Hooks;
+
+	#[test]
+	fn proposal_closed_at_end_block() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&2, 25u32.into());
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			assert_ok!(submit_proposal(1, 5));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+
+			Voting::on_initialize(4);
+			assert_eq!(
+				Voting::get_proposal(&proposal_id).unwrap().status,
+				ProposalStatus::InProgress
+			);
+
+			System::set_block_number(5);
+			Voting::on_initialize(5);
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+			);
+			assert!(pallet_voting::ProposalsEndingAt::<Test>::get(5).is_empty());
+
+			System::set_block_number(6);
+			assert_noop!(
+				Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id),
+				Error::<Test>::ProposalAlreadyEnded
+			);
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+		});
+	}
+
+	#[test]
+	fn end_queue_follows_proposal_updates() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(submit_proposal(1, 5));
+			assert_eq!(
+				pallet_voting::ProposalsEndingAt::<Test>::get(5).to_vec(),
+				vec![proposal_id]
+			);
+
+			assert_ok!(Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 8));
+			assert!(pallet_voting::ProposalsEndingAt::<Test>::get(5).is_empty());
+			assert_eq!(
+				pallet_voting::ProposalsEndingAt::<Test>::get(8).to_vec(),
+				vec![proposal_id]
+			);
+
+			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert!(pallet_voting::ProposalsEndingAt::<Test>::get(8).is_empty());
+			Voting::on_initialize(8);
+			assert_eq!(
+				Voting::get_proposal(&proposal_id).unwrap().status,
+				ProposalStatus::Canceled
+			);
+		});
+	}
+
+	#[test]
+	fn end_queue_limit() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			for _ in 0..MaxProposalsEndingPerBlock::get() {
+				assert_ok!(submit_proposal(1, 5));
+			}
+			assert_noop!(submit_proposal(1, 5), Error::<Test>::TooManyProposalsEnding);
+
+			assert_ok!(submit_proposal(1, 6));
+			let proposal_id = Voting::get_proposal_counter();
+			assert_noop!(
+				Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 5),
+				Error::<Test>::TooManyProposalsEnding
+			);
+		});
+	}
+}
+
+mod sweeper {
+	use super::*;
+	use crate::WeightInfo;
+	use frame_support::{
+		traits::Hooks,
+		weights::{RuntimeDbWeight, Weight},
+	};
+
+	fn before_each() -> (u32, u32) {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&2, 25u32.into());
+		Balances::make_free_balance_be(&3, 25u32.into());
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 3));
+		let first = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		assert_ok!(submit_proposal(1, 5));
+		for proposal_id in [first, first + 1] {
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(3), proposal_id, VoteDecision::Nay(1)));
+		}
+		(first, first + 1)
+	}
+
+	#[test]
+	fn finishes_proposals_and_unlocks_votes() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			assert_eq!(Balances::reserved_balance(&2), 8);
+
+			System::set_block_number(10);
+			Voting::on_idle(10, Weight::MAX);
+			for proposal_id in [first, second] {
+				System::assert_has_event(
+					Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+				);
+				System::assert_has_event(Event::BalanceUnlocked { proposal_id, who: 2 }.into());
+				System::assert_has_event(Event::BalanceUnlocked { proposal_id, who: 3 }.into());
+			}
+			assert_eq!(Balances::free_balance(&2), 25);
+			assert_eq!(Balances::free_balance(&3), 25);
+			assert!(pallet_voting::LockedVotes::<Test>::get(2).is_empty());
+			assert_eq!(pallet_voting::SweptProposals::<Test>::get(), second);
+			assert_eq!(
+				pallet_voting::NextSweep::<Test>::get(),
+				pallet_voting::SweepCursor::Proposals(second + 1)
+			);
+
+			assert_noop!(
+				Voting::unlock_balance(RuntimeOrigin::signed(2), first),
+				Error::<Test>::BalanceAlreadyUnocked
+			);
+		});
+	}
+
+	#[test]
+	fn ongoing_votes_stay_locked() {
+		new_test_ext().execute_with(|| {
+			let (first, _) = before_each();
+
+			System::set_block_number(3);
+			Voting::on_idle(3, Weight::MAX);
+			assert_eq!(Voting::get_proposal(&first).unwrap().status, ProposalStatus::InProgress);
+			assert_eq!(Balances::reserved_balance(&2), 8);
+			assert_eq!(pallet_voting::LockedVotes::<Test>::get(2).len(), 2);
+		});
+	}
+
+	#[test]
+	fn resumes_from_cursor() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			MaxEnactmentWeight::set(Weight::zero());
+
+			//Only enough weight to finish one proposal
+			System::set_block_number(10);
+			let check =
+				<() as WeightInfo>::on_idle(1).saturating_sub(<() as WeightInfo>::on_idle(0));
+			let limit =
+				<() as WeightInfo>::finish_proposal().saturating_add(check.saturating_mul(2));
+			Voting::on_idle(10, limit);
+			assert_eq!(Voting::get_proposal(&first).unwrap().status, ProposalStatus::Passed);
+			assert_eq!(Voting::get_proposal(&second).unwrap().status, ProposalStatus::InProgress);
+			assert_eq!(
+				pallet_voting::NextSweep::<Test>::get(),
+				pallet_voting::SweepCursor::Proposals(second)
+			);
+
+			//Not enough weight to do anything
+			Voting::on_idle(10, Weight::zero());
+			assert_eq!(Voting::get_proposal(&second).unwrap().status, ProposalStatus::InProgress);
+
+			Voting::on_idle(10, Weight::MAX);
+			assert_eq!(Voting::get_proposal(&second).unwrap().status, ProposalStatus::Passed);
+			assert_eq!(Balances::free_balance(&2), 25);
+		});
+	}
+
+	#[test]
+	fn closed_proposals_not_checked_again() {
+		new_test_ext().execute_with(|| {
+			let (_, second) = before_each();
+			let running = second + 1;
+			assert_ok!(submit_proposal(1, 20));
+			assert_ok!(submit_proposal(1, 5));
+
+			//The running proposal holds the mark back
+			System::set_block_number(10);
+			Voting::on_idle(10, Weight::MAX);
+			assert_eq!(pallet_voting::SweptProposals::<Test>::get(), second);
+			assert_eq!(
+				pallet_voting::NextSweep::<Test>::get(),
+				pallet_voting::SweepCursor::Proposals(running)
+			);
+
+			System::set_block_number(20);
+			Voting::on_initialize(20);
+			System::set_block_number(21);
+			Voting::on_idle(21, Weight::MAX);
+			assert_eq!(pallet_voting::SweptProposals::<Test>::get(), running + 1);
+		});
+	}
+
+	#[test]
+	fn never_exceeds_remaining_weight() {
+		new_test_ext().execute_with(|| {
+			DbWeight::set(RuntimeDbWeight { read: 1_000, write: 10_000 });
+			MaxEnactmentWeight::set(Weight::zero());
+			before_each();
+			for _ in 0..5 {
+				assert_ok!(submit_proposal(1, 5));
+			}
+
+			System::set_block_number(10);
+			let finish = <() as WeightInfo>::finish_proposal();
+			for limit in [
+				Weight::zero(),
+				Weight::from_ref_time(1_000),
+				Weight::from_ref_time(25_000),
+				finish,
+				finish.saturating_mul(3),
+				Weight::from_ref_time(1_000),
+				<() as WeightInfo>::unlock_balance().saturating_mul(3),
+			] {
+				assert!(Voting::on_idle(10, limit).all_lte(limit));
+			}
+		});
+	}
+
+	#[test]
+	fn closed_proposals_do_not_reserve_enactment_weight() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			MaxEnactmentWeight::set(Weight::MAX);
+
+			System::set_block_number(5);
+			Voting::on_initialize(5);
+			assert_eq!(Voting::get_proposal(&second).unwrap().status, ProposalStatus::Passed);
+
+			//Only enough weight to check the proposals and unlock the four votes
+			System::set_block_number(6);
+			let check =
+				<() as WeightInfo>::on_idle(1).saturating_sub(<() as WeightInfo>::on_idle(0));
+			let limit = <() as WeightInfo>::unlock_balance()
+				.saturating_mul(4)
+				.saturating_add(check.saturating_mul(3));
+			Voting::on_idle(6, limit);
+			for proposal_id in [first, second] {
+				System::assert_has_event(Event::BalanceUnlocked { proposal_id, who: 3 }.into());
+			}
+			assert_eq!(Balances::free_balance(&2), 25);
+		});
+	}
+}
+
+mod quorum {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=4 {
+			Balances::make_free_balance_be(&who, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		proposal_id
+	}
+
+	#[test]
+	fn voters_counted() {
+		new_test_ext().execute_with(|| {
+			VoteRemovalThreshold::set(0);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Nay(1)));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().voters, 2);
+
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(2)
+			));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().voters, 2);
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().voters, 1);
+		});
+	}
+
+	#[test]
+	fn not_enough_voters() {
+		new_test_ext().execute_with(|| {
+			QuorumVoters::set(Perbill::from_percent(50));
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(3)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::QuorumNotMet }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn not_enough_points() {
+		new_test_ext().execute_with(|| {
+			QuorumPoints::set(3);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::QuorumNotMet }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn eligible_voters_in_base() {
+		new_test_ext().execute_with(|| {
+			QuorumVoters::set(Perbill::from_percent(50));
+			let first = before_each();
+			EligibleAccounts::set(vec![5, 6, 7, 8]);
+			for who in 5..=8 {
+				Balances::make_free_balance_be(&who, 25u32.into());
+				assert_ok!(Voting::vote(RuntimeOrigin::signed(who), first, VoteDecision::Aye(1)));
+			}
+			assert_eq!(pallet_voting::AmountEligibleVoters::<Test>::get(), 4);
+
+			//Two of the four registered voters are not enough out of eight voters
+			let second = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_proposal(1, 5));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), second, VoteDecision::Aye(1)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), second, VoteDecision::Aye(1)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), first));
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), second));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id: first, status: ProposalStatus::Passed }.into(),
+			);
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id: second, status: ProposalStatus::QuorumNotMet }
+					.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn quorum_reached() {
+		new_test_ext().execute_with(|| {
+			QuorumVoters::set(Perbill::from_percent(50));
+			QuorumPoints::set(3);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(2)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Nay(1)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+			);
+		});
+	}
+}
+
+mod abstain {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=4 {
+			Balances::make_free_balance_be(&who, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		proposal_id
+	}
+
+	#[test]
+	fn counts_toward_quorum_only() {
+		new_test_ext().execute_with(|| {
+			QuorumVoters::set(Perbill::from_percent(50));
+			QuorumPoints::set(3);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Nay(1)));
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Abstain(2)
+			));
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.abstains), (0, 1, 2));
+			assert_eq!((proposal.voters, proposal.points()), (2, 3));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Rejected }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn update_cancel_and_unlock() {
+		new_test_ext().execute_with(|| {
+			VoteRemovalThreshold::set(0);
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Abstain(3)
+			));
+			assert_eq!(Balances::reserved_balance(&2), 9);
+
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(2)
+			));
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.abstains), (2, 0));
+			assert_eq!(Balances::reserved_balance(&2), 4);
+
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Abstain(1)
+			));
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.abstains, proposal.voters), (0, 0, 0));
+			assert_eq!(Balances::reserved_balance(&2), 0);
+
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(3),
+				proposal_id,
+				VoteDecision::Abstain(2)
+			));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(3), proposal_id));
+			assert_eq!(Balances::reserved_balance(&3), 0);
+		});
+	}
+
+	#[test]
+	fn accepted_by_multi_option_proposals() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			Balances::make_free_balance_be(&1, 25u32.into());
+			assert_ok!(Voting::make_proposal(
+				RuntimeOrigin::signed(1),
+				sp_core::H256::zero(),
+				ProposalParams { options: vec![b"A".to_vec(), b"B".to_vec()], ..params() },
+				5,
+				None,
+			));
+			let proposal_id = Voting::get_proposal_counter();
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(1),
+				proposal_id,
+				VoteDecision::Abstain(1)
+			));
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.abstains, proposal.tallies.into_inner()), (1, vec![0, 0]));
+		});
+	}
+}
+
+mod split_votes {
+	use super::*;
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=2 {
+			Balances::make_free_balance_be(&who, 100u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		proposal_id
+	}
+
+	fn tally(proposal_id: u32) -> (u32, u32) {
+		let proposal = Voting::get_proposal(&proposal_id).unwrap();
+		(proposal.ayes, proposal.nays)
+	}
+
+	#[test]
+	fn costs_combined_points() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Split { aye: 3, nay: 2 }
+			));
+			assert_eq!(tally(proposal_id), (3, 2));
+			assert_eq!(Voting::get_proposal(&proposal_id).unwrap().voters, 1);
+			assert_eq!(Balances::reserved_balance(&2), 25);
+
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(1),
+					proposal_id,
+					VoteDecision::Split { aye: 4, nay: 4 }
+				),
+				Error::<Test>::VoteAmountLimit
+			);
+			assert_noop!(
+				Voting::vote(
+					RuntimeOrigin::signed(1),
+					proposal_id,
+					VoteDecision::Split { aye: 0, nay: 0 }
+				),
+				Error::<Test>::InvalidVoteAmount
+			);
+		});
+	}
+
+	#[test]
+	fn update_between_split_and_single() {
+		new_test_ext().execute_with(|| {
+			// The removal threshold is already passed, so only reductions are rejected.
+			let proposal_id = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(5)));
+
+			let split = VoteDecision::Split { aye: 3, nay: 2 };
+			assert_ok!(Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, split));
+			assert_eq!(tally(proposal_id), (3, 2));
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(5)
+			));
+			assert_eq!(tally(proposal_id), (0, 5));
+			assert_eq!(Balances::reserved_balance(&2), 25);
+
+			assert_noop!(
+				Voting::update_vote(
+					RuntimeOrigin::signed(2),
+					proposal_id,
+					VoteDecision::Split { aye: 2, nay: 2 }
+				),
+				Error::<Test>::PassedRemovalThreshold
+			);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Split { aye: 1, nay: 5 }
+			));
+			assert_eq!(tally(proposal_id), (1, 5));
+			assert_eq!(Balances::reserved_balance(&2), 36);
+		});
+	}
+}
+
+mod approval_threshold {
+	use super::*;
+	use sp_runtime::Perbill;
+
+	fn before_each() {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			Balances::make_free_balance_be(&who, 100u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+	}
+
+	// Submits a proposal, casts the given votes and finishes it.
+	fn run_proposal(
+		threshold: ApprovalThreshold,
+		votes: &[(u64, VoteDecision<MaxOptions>)],
+	) -> ProposalStatus {
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		let end = System::block_number() + 5;
+		assert_ok!(submit_proposal_with_threshold(1, end, threshold));
+		for (who, decision) in votes {
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(*who), proposal_id, decision.clone()));
+		}
+		System::set_block_number(end + 1);
+		assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+		Voting::get_proposal(&proposal_id).unwrap().status
+	}
+
+	#[test]
+	fn threshold_not_allowed() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let threshold = ApprovalThreshold::SuperMajority(Perbill::from_percent(40));
+			assert_noop!(
+				submit_proposal_with_threshold(1, 5, threshold),
+				Error::<Test>::ThresholdNotAllowed
+			);
+		});
+	}
+
+	#[test]
+	fn threshold_stored() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			assert_ok!(submit_proposal_with_threshold(1, 5, ApprovalThreshold::Unanimity));
+			let proposal_id = Voting::get_proposal_counter();
+			assert_eq!(
+				Voting::get_proposal(&proposal_id).unwrap().threshold,
+				ApprovalThreshold::Unanimity
+			);
+		});
+	}
+
+	#[test]
+	fn super_majority() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let threshold = ApprovalThreshold::SuperMajority(Perbill::from_percent(67));
+			let status =
+				run_proposal(threshold, &[(2, VoteDecision::Aye(3)), (3, VoteDecision::Nay(2))]);
+			assert_eq!(status, ProposalStatus::Rejected);
+
+			let status =
+				run_proposal(threshold, &[(2, VoteDecision::Aye(5)), (3, VoteDecision::Nay(2))]);
+			assert_eq!(status, ProposalStatus::Passed);
+		});
+	}
+
+	#[test]
+	fn unanimity() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let status = run_proposal(
+				ApprovalThreshold::Unanimity,
+				&[(1, VoteDecision::Aye(3)), (2, VoteDecision::Aye(3)), (3, VoteDecision::Nay(1))],
+			);
+			assert_eq!(status, ProposalStatus::Rejected);
+
+			let status = run_proposal(
+				ApprovalThreshold::Unanimity,
+				&[(1, VoteDecision::Aye(1)), (2, VoteDecision::Aye(1))],
+			);
+			assert_eq!(status, ProposalStatus::Passed);
+		});
+	}
+
+	#[test]
+	fn tally() {
+		let super_majority = ApprovalThreshold::SuperMajority(Perbill::from_percent(60));
+		assert_eq!(ApprovalThreshold::SimpleMajority.tally(2, 1), ProposalStatus::Passed);
+		assert_eq!(ApprovalThreshold::SimpleMajority.tally(1, 2), ProposalStatus::Rejected);
+		assert_eq!(super_majority.tally(3, 2), ProposalStatus::Passed);
+		assert_eq!(super_majority.tally(5, 4), ProposalStatus::Rejected);
+		assert_eq!(ApprovalThreshold::Unanimity.tally(7, 0), ProposalStatus::Passed);
+		assert_eq!(ApprovalThreshold::Unanimity.tally(7, 1), ProposalStatus::Rejected);
+		assert_eq!(ApprovalThreshold::Unanimity.tally(0, 0), ProposalStatus::Tied);
+	}
+}
+
+mod multi_option {
+	use super::*;
+	use frame_support::BoundedVec;
+
+	fn before_each() {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			Balances::make_free_balance_be(&who, 100u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+	}
+
+	fn submit_options_proposal(options: &[&[u8]]) -> DispatchResult {
+		submit_options_proposal_with(options, ApprovalThreshold::SimpleMajority)
+	}
+
+	fn submit_options_proposal_with(
+		options: &[&[u8]],
+		threshold: ApprovalThreshold,
+	) -> DispatchResult {
+		Voting::make_proposal(
+			RuntimeOrigin::signed(1),
+			sp_core::H256::zero(),
+			ProposalParams {
+				options: options.iter().map(|option| option.to_vec()).collect(),
+				threshold,
+				..params()
+			},
+			5,
+			None,
+		)
+	}
+
+	fn options(points: &[u32]) -> VoteDecision<MaxOptions> {
+		VoteDecision::Options(BoundedVec::truncate_from(points.to_vec()))
+	}
+
+	fn tallies(proposal_id: u32) -> Vec<u32> {
+		Voting::get_proposal(&proposal_id).unwrap().tallies.into_inner()
+	}
+
+	#[test]
+	fn invalid_options() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			assert_noop!(submit_options_proposal(&[b"Red"]), Error::<Test>::TooFewOptions);
+			assert_noop!(
+				submit_options_proposal(&[b"A", b"B", b"C", b"D", b"E"]),
+				Error::<Test>::TooManyOptions
+			);
+			assert_noop!(
+				submit_options_proposal(&[b"Red", &[b'a'; 17]]),
+				Error::<Test>::OptionTooLong
+			);
+			assert_noop!(
+				submit_options_proposal_with(&[b"Red", b"Blue"], ApprovalThreshold::Unanimity),
+				Error::<Test>::InvalidOptionsProposal
+			);
+		});
+	}
+
+	#[test]
+	fn quadratic_cost() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_options_proposal(&[b"Red", b"Blue", b"Green"]));
+			assert_eq!(tallies(proposal_id), vec![0, 0, 0]);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, options(&[2, 1, 0])));
+			assert_eq!(Balances::reserved_balance(&2), 5);
+			assert_eq!(tallies(proposal_id), vec![2, 1, 0]);
+
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				options(&[0, 3, 1])
+			));
+			assert_eq!(Balances::reserved_balance(&2), 10);
+			assert_eq!(tallies(proposal_id), vec![0, 3, 1]);
+		});
+	}
+
+	#[test]
+	fn invalid_vote() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_options_proposal(&[b"Red", b"Blue", b"Green"]));
+			assert_ok!(submit_proposal(1, 5));
+
+			let vote = |decision| Voting::vote(RuntimeOrigin::signed(2), proposal_id, decision);
+			assert_noop!(vote(VoteDecision::Aye(1)), Error::<Test>::InvalidVoteOptions);
+			assert_noop!(vote(options(&[1, 1])), Error::<Test>::InvalidVoteOptions);
+			assert_noop!(vote(options(&[0, 0, 0])), Error::<Test>::InvalidVoteAmount);
+			assert_noop!(vote(options(&[8, 0, 0])), Error::<Test>::VoteAmountLimit);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id + 1, options(&[1, 1, 1])),
+				Error::<Test>::InvalidVoteOptions
+			);
+		});
+	}
+
+	#[test]
+	fn option_won() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_options_proposal(&[b"Red", b"Blue", b"Green"]));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, options(&[3, 0, 0])));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, options(&[0, 2, 1])));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(3), proposal_id, options(&[0, 2, 0])));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::OptionsTallied {
+					proposal_id,
+					tallies: BoundedVec::truncate_from(vec![3, 4, 1]),
+				}
+				.into(),
+			);
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Won(1) }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn options_tied() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_options_proposal(&[b"Red", b"Blue", b"Green"]));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, options(&[3, 0, 0])));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, options(&[0, 2, 1])));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(3), proposal_id, options(&[0, 1, 0])));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			System::assert_has_event(
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Tied }.into(),
+			);
+		});
+	}
+
+	#[test]
+	fn cancel_and_unlock() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			VoteRemovalThreshold::set(0);
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_options_proposal(&[b"Red", b"Blue"]));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, options(&[2, 2])));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(3), proposal_id, options(&[1, 0])));
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(tallies(proposal_id), vec![1, 0]);
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(3), proposal_id));
+			assert_eq!(Balances::reserved_balance(&3), 0);
+		});
+	}
+}
+
+mod voice_credits {
+	use super::*;
+	use crate::CollateralMode;
+
+	fn before_each(time_period: u64) -> u32 {
+		System::set_block_number(1);
+		VoteCollateral::set(CollateralMode::Credits);
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, time_period));
+		proposal_id
+	}
+
+	#[test]
+	fn credits_paid_instead_of_balance() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each(30);
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+			assert_eq!(Voting::credits_of(&2).credits, 11);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+		});
+	}
+
+	#[test]
+	fn not_enough_credits() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each(30);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(5)),
+				Error::<Test>::NotEnoughCredits
+			);
+		});
+	}
+
+	#[test]
+	fn update_and_cancel_refund_credits() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each(30);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(4)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 4);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(2)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 16);
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+		});
+	}
+
+	#[test]
+	fn credits_spent_when_proposal_ends() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each(5);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::credits_of(&2).credits, 11);
+		});
+	}
+
+	#[test]
+	fn new_budget_every_round() {
+		new_test_ext().execute_with(|| {
+			CreditRoundLength::set(Some(10));
+			let proposal_id = before_each(40);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(4)));
+			assert_eq!(
+				Voting::credits_of(&2),
+				pallet_voting::CreditBalance { round: 0, credits: 4 }
+			);
+
+			System::set_block_number(10);
+			assert_eq!(
+				Voting::credits_of(&2),
+				pallet_voting::CreditBalance { round: 1, credits: 20 }
+			);
+
+			// Credits spent in an earlier round are not refunded.
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+		});
+	}
+
+	#[test]
+	fn update_keeps_round_of_vote() {
+		new_test_ext().execute_with(|| {
+			CreditRoundLength::set(Some(10));
+			VoteRemovalThreshold::set(0);
+			let proposal_id = before_each(40);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(4)));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(4)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+			assert_eq!(pallet_voting::Votes::<Test>::get(2, proposal_id).unwrap().round, 0);
+
+			// Lowering the vote in a later round refunds nothing into the new budget.
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(2)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+		});
+	}
+
+	#[test]
+	fn increase_paid_by_later_round() {
+		new_test_ext().execute_with(|| {
+			CreditRoundLength::set(Some(10));
+			VoteRemovalThreshold::set(0);
+			let proposal_id = before_each(40);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+
+			System::set_block_number(10);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(4)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 13);
+			let vote = pallet_voting::Votes::<Test>::get(2, proposal_id).unwrap();
+			assert_eq!((vote.round, vote.paid), (1, 7));
+
+			// The increase is refunded into the round that paid for it.
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(3)
+			));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+
+			// Cancels refund what this round paid, not the credits of the first round.
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(4)
+			));
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(Voting::credits_of(&2).credits, 20);
+		});
+	}
+
+	#[test]
+	fn issue_credits() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each(30);
+			assert_noop!(
+				Voting::issue_credits(RuntimeOrigin::signed(2), 2, 50),
+				sp_runtime::DispatchError::BadOrigin
+			);
+			assert_noop!(
+				Voting::issue_credits(RuntimeOrigin::root(), 3, 50),
+				Error::<Test>::VoterIsNotRegistered
+			);
+
+			assert_ok!(Voting::issue_credits(RuntimeOrigin::root(), 2, 50));
+			System::assert_last_event(
+				Event::CreditsIssued { who: 2, round: 0, credits: 50 }.into(),
+			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(7)));
+			assert_eq!(Voting::credits_of(&2).credits, 1);
+		});
+	}
+}
+
+mod vote_cost {
+	use super::*;
+	use crate::{Linear, Quadratic, ScaledQuadratic, VoteCost};
+	use frame_support::traits::ConstU32;
+
+	#[test]
+	fn cost_curves() {
+		assert_eq!(<Quadratic as VoteCost<u128>>::cost(7), Some(49));
+		assert_eq!(<Linear as VoteCost<u128>>::cost(7), Some(7));
+		assert_eq!(<ScaledQuadratic<ConstU32<10>> as VoteCost<u128>>::cost(7), Some(490));
+		assert_eq!(<Quadratic as VoteCost<u32>>::cost(u32::MAX), None);
+		assert_eq!(
+			<Quadratic as VoteCost<u128>>::cost(u32::MAX),
+			Some(u32::MAX as u128 * u32::MAX as u128)
+		);
+
+		let decision: VoteDecision<MaxOptions> =
+			VoteDecision::Options(frame_support::BoundedVec::truncate_from(vec![1, 2, 3]));
+		assert_eq!(decision.cost::<u128, Quadratic>(), Some(14));
+		assert_eq!(decision.cost::<u128, Linear>(), Some(6));
+	}
+
+	#[test]
+	fn cost_applied_consistently() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			LinearCost::set(true);
+			Balances::make_free_balance_be(&2, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			let first = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_proposal(1, 30));
+			assert_ok!(submit_proposal(1, 30));
+			let second = first + 1;
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(5)));
+			assert_eq!(Balances::reserved_balance(&2), 5);
+			assert_ok!(Voting::update_vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(7)));
+			assert_eq!(Balances::reserved_balance(&2), 7);
+			assert_ok!(Voting::update_vote(RuntimeOrigin::signed(2), first, VoteDecision::Nay(3)));
+			assert_eq!(Balances::reserved_balance(&2), 3);
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), second, VoteDecision::Aye(6)));
+			assert_eq!(Balances::reserved_balance(&2), 9);
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), second));
+			assert_eq!(Balances::reserved_balance(&2), 3);
+
+			System::set_block_number(31);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), first));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), first));
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(Balances::free_balance(&2), 25);
+		});
+	}
+
+	#[test]
+	fn priced_per_point_squared() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			CostPerPointSquared::set(1_000);
+			Balances::make_free_balance_be(&2, 10_000u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+			let proposal_id = Voting::get_proposal_counter() + 1;
+			assert_ok!(submit_proposal(1, 30));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+			assert_eq!(Balances::reserved_balance(&2), 9_000);
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(4)),
+				pallet_balances::Error::<Test>::InsufficientBalance
+			);
+
+			CostPerPointSquared::set(u128::MAX);
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(4)),
+				Error::<Test>::Overflow
+			);
+		});
+	}
+}
+
+mod vote_lock {
+	use super::*;
+	use crate::CollateralMode;
+	use frame_support::traits::{ExistenceRequirement, WithdrawReasons};
+
+	fn before_each() -> (u32, u32) {
+		System::set_block_number(1);
+		VoteCollateral::set(CollateralMode::Lock);
+		Balances::make_free_balance_be(&2, 25u32.into());
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+		let first = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 5));
+		assert_ok!(submit_proposal(1, 10));
+		(first, first + 1)
+	}
+
+	#[test]
+	fn one_lock_backs_every_vote() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(4)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), second, VoteDecision::Nay(3)));
+			assert_eq!(Voting::vote_lock(&2), 16);
+			assert_eq!(Balances::locks(&2)[0].amount, 16);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+
+			assert_ok!(Voting::update_vote(RuntimeOrigin::signed(2), second, VoteDecision::Nay(5)));
+			assert_eq!(Voting::vote_lock(&2), 25);
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), second));
+			assert_eq!(Voting::vote_lock(&2), 16);
+		});
+	}
+
+	#[test]
+	fn lock_must_be_covered() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			Balances::make_free_balance_be(&2, 20u32.into());
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(4)));
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), second, VoteDecision::Aye(5)),
+				Error::<Test>::InsufficientFunds
+			);
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(5)),
+				Error::<Test>::InsufficientFunds
+			);
+		});
+	}
+
+	#[test]
+	fn lock_removed_once_votes_unlocked() {
+		new_test_ext().execute_with(|| {
+			let (first, second) = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(4)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), second, VoteDecision::Aye(2)));
+
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), first));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), first));
+			assert_eq!(Voting::vote_lock(&2), 4);
+
+			System::set_block_number(11);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), second));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), second));
+			assert_eq!(Voting::vote_lock(&2), 0);
+			assert!(Balances::locks(&2).is_empty());
+		});
+	}
+
+	#[test]
+	fn lock_only_blocks_transfers() {
+		new_test_ext().execute_with(|| {
+			let (first, _) = before_each();
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), first, VoteDecision::Aye(4)));
+			assert_noop!(
+				<Balances as Currency<u64>>::transfer(&2, &3, 10, ExistenceRequirement::KeepAlive),
+				pallet_balances::Error::<Test>::LiquidityRestrictions
+			);
+			assert_ok!(Balances::withdraw(
+				&2,
+				20,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::KeepAlive
+			));
+		});
+	}
+}
+
+mod delegation {
+	use super::*;
+	use crate::DelegationScope;
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=6 {
+			Balances::make_free_balance_be(&who, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 30));
+		proposal_id
+	}
+
+	fn delegate(who: u64, to: u64, scope: DelegationScope) -> DispatchResult {
+		Voting::delegate(RuntimeOrigin::signed(who), to, scope)
+	}
+
+	#[test]
+	fn vote_cast_for_delegators() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(delegate(4, 2, DelegationScope::Proposal(proposal_id)));
+			assert_ok!(delegate(5, 2, DelegationScope::Proposal(proposal_id + 1)));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(4), proposal_id, VoteDecision::Nay(1)));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			System::assert_last_event(
+				Event::DelegatedVoteCast { proposal_id, delegate: 2, who: 3 }.into(),
+			);
+			assert_eq!(Balances::reserved_balance(&3), 4);
+			assert!(!Voting::vote_casted(&5, &proposal_id));
+
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (4, 1, 3));
+		});
+	}
+
+	#[test]
+	fn delegators_that_cannot_pay_are_skipped() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			Balances::make_free_balance_be(&3, 3u32.into());
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(delegate(4, 2, DelegationScope::All));
+
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			assert!(!Voting::vote_casted(&3, &proposal_id));
+			assert!(Voting::vote_casted(&4, &proposal_id));
+		});
+	}
+
+	#[test]
+	fn delegated_votes_follow_updates_and_cancels() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(3)
+			));
+			assert_eq!(
+				pallet_voting::Votes::<Test>::get(3, proposal_id).unwrap().vote_decision,
+				VoteDecision::Nay(3)
+			);
+			assert_eq!(Balances::reserved_balance(&3), 9);
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert!(!Voting::vote_casted(&3, &proposal_id));
+			assert_eq!(Balances::reserved_balance(&3), 0);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (0, 0, 0));
+		});
+	}
+
+	#[test]
+	fn delegator_that_cannot_pay_update_keeps_vote() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			Balances::make_free_balance_be(&3, 10u32.into());
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+
+			//Past the removal threshold the old vote could not be cast again
+			System::set_block_number(15);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Nay(4)
+			));
+
+			let vote = pallet_voting::Votes::<Test>::get(3, proposal_id).unwrap();
+			assert_eq!(vote.vote_decision, VoteDecision::Aye(2));
+			assert!(vote.delegated);
+			assert_eq!(Balances::reserved_balance(&3), 4);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (2, 4, 2));
+		});
+	}
+
+	#[test]
+	fn updated_delegated_vote_is_kept() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(2)));
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(3),
+				proposal_id,
+				VoteDecision::Nay(1)
+			));
+
+			assert_ok!(Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id));
+			assert_eq!(
+				pallet_voting::Votes::<Test>::get(3, proposal_id).unwrap().vote_decision,
+				VoteDecision::Nay(1)
+			);
+		});
+	}
+
+	#[test]
+	fn own_vote_replaces_delegated_vote() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)));
+			assert_eq!(Balances::reserved_balance(&3), 9);
+
+			//Lowering the vote is subject to the removal threshold
+			System::set_block_number(15);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(3), proposal_id, VoteDecision::Nay(2)),
+				Error::<Test>::PassedRemovalThreshold
+			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(3), proposal_id, VoteDecision::Nay(4)));
+
+			let vote = pallet_voting::Votes::<Test>::get(3, proposal_id).unwrap();
+			assert_eq!(vote.vote_decision, VoteDecision::Nay(4));
+			assert!(!vote.delegated);
+			assert_eq!(Balances::reserved_balance(&3), 16);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (3, 4, 2));
+
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(3), proposal_id, VoteDecision::Nay(5)),
+				Error::<Test>::VoteAlreadyCasted
+			);
+			assert_ok!(Voting::update_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(4)
+			));
+			assert_eq!(
+				pallet_voting::Votes::<Test>::get(3, proposal_id).unwrap().vote_decision,
+				VoteDecision::Nay(4)
+			);
+		});
+	}
+
+	#[test]
+	fn cycles_rejected() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			assert_noop!(delegate(2, 2, DelegationScope::All), Error::<Test>::DelegationCycle);
+			assert_ok!(delegate(2, 3, DelegationScope::All));
+			assert_ok!(delegate(3, 4, DelegationScope::All));
+			assert_noop!(delegate(4, 2, DelegationScope::All), Error::<Test>::DelegationCycle);
+
+			assert_ok!(delegate(4, 5, DelegationScope::All));
+			assert_noop!(delegate(6, 2, DelegationScope::All), Error::<Test>::DelegationTooDeep);
+		});
+	}
+
+	#[test]
+	fn delegation_limits() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			assert_noop!(
+				delegate(2, 7, DelegationScope::All),
+				Error::<Test>::DelegateNotRegistered
+			);
+			for who in 3..=5 {
+				assert_ok!(delegate(who, 2, DelegationScope::All));
+			}
+			assert_noop!(delegate(6, 2, DelegationScope::All), Error::<Test>::TooManyDelegators);
+		});
+	}
+
+	#[test]
+	fn delegation_replaced_and_removed() {
+		new_test_ext().execute_with(|| {
+			before_each();
+			assert_noop!(
+				Voting::undelegate(RuntimeOrigin::signed(3)),
+				Error::<Test>::NotDelegating
+			);
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(delegate(3, 4, DelegationScope::All));
+			assert!(Voting::delegators_of(&2).is_empty());
+			assert_eq!(Voting::delegators_of(&4).to_vec(), vec![3]);
+
+			assert_ok!(Voting::undelegate(RuntimeOrigin::signed(3)));
+			System::assert_last_event(Event::Undelegated { who: 3 }.into());
+			assert!(Voting::delegation_of(&3).is_none());
+			assert!(Voting::delegators_of(&4).is_empty());
+
+			assert_ok!(delegate(3, 2, DelegationScope::All));
+			assert_ok!(delegate(2, 4, DelegationScope::All));
+			assert_ok!(Voting::remove_voter(RuntimeOrigin::root(), 2));
+			assert!(Voting::delegation_of(&3).is_none());
+			assert!(Voting::delegators_of(&4).is_empty());
+		});
+	}
+}
+
+mod signed_votes {
+	use super::*;
+	use crate::SignedVotePayload;
+	use codec::Encode;
+	use sp_runtime::{testing::TestSignature, DispatchError};
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			Balances::make_free_balance_be(&who, 25u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_proposal(1, 30));
+		proposal_id
+	}
+
+	fn signed_vote(
+		who: u64,
+		proposal_id: u32,
+		vote_decision: VoteDecision<MaxOptions>,
+		nonce: u32,
+	) -> (u64, VoteDecision<MaxOptions>, u32, TestSignature) {
+		signed_vote_on(System::block_hash(0), who, proposal_id, vote_decision, nonce)
+	}
+
+	fn signed_vote_on(
+		genesis_hash: sp_core::H256,
+		who: u64,
+		proposal_id: u32,
+		vote_decision: VoteDecision<MaxOptions>,
+		nonce: u32,
+	) -> (u64, VoteDecision<MaxOptions>, u32, TestSignature) {
+		let payload = SignedVotePayload {
+			genesis_hash,
+			proposal_id,
+			vote_decision: vote_decision.clone(),
+			nonce,
+		};
+		(who, vote_decision, nonce, TestSignature(who, payload.encode()))
+	}
+
+	#[test]
+	fn batch_applied() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			let votes = vec![
+				signed_vote(2, proposal_id, VoteDecision::Aye(3), 0),
+				signed_vote(3, proposal_id, VoteDecision::Nay(2), 0),
+			];
+			assert_ok!(Voting::submit_signed_votes(RuntimeOrigin::signed(9), proposal_id, votes));
+			System::assert_last_event(
+				Event::SignedVotesSubmitted { proposal_id, cast: 2, rejected: 0 }.into(),
+			);
+
+			assert_eq!(Balances::reserved_balance(&2), 9);
+			assert_eq!(Voting::signed_vote_nonce(&2), 1);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (3, 2, 2));
+		});
+	}
+
+	#[test]
+	fn failed_entries_reported() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			let applied = signed_vote(2, proposal_id, VoteDecision::Aye(3), 0);
+			let (who, vote_decision, nonce, signature) =
+				signed_vote(3, proposal_id, VoteDecision::Aye(3), 0);
+			let forged = (who, vote_decision, nonce, TestSignature(2, signature.1));
+			let unaffordable = signed_vote(3, proposal_id, VoteDecision::Nay(6), 0);
+			let votes = vec![
+				applied.clone(),
+				forged,
+				signed_vote(1, proposal_id, VoteDecision::Aye(1), 1),
+				unaffordable.clone(),
+			];
+			assert_ok!(Voting::submit_signed_votes(RuntimeOrigin::signed(9), proposal_id, votes));
+
+			let rejected = |who, error: DispatchError| {
+				System::assert_has_event(
+					Event::SignedVoteRejected { proposal_id, who, error }.into(),
+				);
+			};
+			rejected(3, Error::<Test>::InvalidSignature.into());
+			rejected(1, Error::<Test>::InvalidNonce.into());
+			rejected(3, pallet_balances::Error::<Test>::InsufficientBalance.into());
+			System::assert_last_event(
+				Event::SignedVotesSubmitted { proposal_id, cast: 1, rejected: 3 }.into(),
+			);
+			// The nonce of a failed vote is used up too.
+			assert_eq!(Voting::signed_vote_nonce(&1), 0);
+			assert_eq!(Voting::signed_vote_nonce(&3), 1);
+
+			// Neither the applied vote nor the failed one can be replayed, even once the voter
+			// could pay for it.
+			Balances::make_free_balance_be(&3, 100u32.into());
+			assert_ok!(Voting::submit_signed_votes(
+				RuntimeOrigin::signed(9),
+				proposal_id,
+				vec![applied, unaffordable]
+			));
+			rejected(2, Error::<Test>::InvalidNonce.into());
+			rejected(3, Error::<Test>::InvalidNonce.into());
+			assert_eq!(Balances::reserved_balance(&2), 9);
+			assert!(!Voting::vote_casted(&3, &proposal_id));
+		});
+	}
+
+	#[test]
+	fn signed_for_another_chain() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			let vote = signed_vote_on(
+				sp_core::H256::repeat_byte(1),
+				2,
+				proposal_id,
+				VoteDecision::Aye(3),
+				0,
+			);
+			assert_ok!(Voting::submit_signed_votes(
+				RuntimeOrigin::signed(9),
+				proposal_id,
+				vec![vote]
+			));
+			System::assert_has_event(
+				Event::SignedVoteRejected {
+					proposal_id,
+					who: 2,
+					error: Error::<Test>::InvalidSignature.into(),
+				}
+				.into(),
+			);
+			assert_eq!(Voting::signed_vote_nonce(&2), 0);
+		});
+	}
+
+	#[test]
+	fn batch_limit() {
+		new_test_ext().execute_with(|| {
+			let proposal_id = before_each();
+			let votes = (0..=MaxSignedVotes::get())
+				.map(|nonce| signed_vote(2, proposal_id, VoteDecision::Aye(1), nonce))
+				.collect();
+			assert_noop!(
+				Voting::submit_signed_votes(RuntimeOrigin::signed(9), proposal_id, votes),
+				Error::<Test>::TooManySignedVotes
+			);
+		});
+	}
+}
+
+mod secret_ballots {
+	use super::*;
+
+	const SALT: [u8; 32] = [7; 32];
+
+	fn before_each() -> u32 {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			Balances::make_free_balance_be(&who, 100u32.into());
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), who));
+		}
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(submit_secret_proposal(10));
+		proposal_id
+	}
+
+	fn submit_secret_proposal(time_period: u64) -> DispatchResult {
+		Voting::make_proposal(
+			RuntimeOrigin::signed(1),
+			sp_core::H256::zero(),
+			ProposalParams { secret: true, ..params() },
+			time_period,
+			None,
+		)
+	}
+
+	fn commit(
+		who: u64,
+		proposal_id: u32,
+		vote_decision: &VoteDecision<MaxOptions>,
+	) -> DispatchResult {
+		let commitment = Voting::vote_commitment(&who, proposal_id, vote_decision, &SALT);
+		Voting::commit_vote(RuntimeOrigin::signed(who), proposal_id, commitment)
+	}
 
 	#[test]
-	fn cast_valid_votes() {
+	fn commit_and_reveal() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
-			let initial_balance: u32 = 25;
-			Balances::make_free_balance_be(&1, initial_balance.into());
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
-
-			//Vote in favor and verify that the functions excecutes properly and the event is
-			// created
-			let vote_amount: u32 = 2;
-			assert_ok!(Voting::vote(
-				RuntimeOrigin::signed(1),
-				proposal_id,
-				VoteDecision::Aye(vote_amount)
-			));
-			System::assert_has_event(Event::VoteCasted { proposal_id, who: 1 }.into());
-
-			//Check that the reserved amount from the user is (amount of votes^2)
-			let user_balance = Balances::free_balance(&1);
-			assert_eq!(initial_balance, user_balance as u32 + vote_amount.pow(2));
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(3)));
+			System::assert_last_event(Event::VoteCommitted { proposal_id, who: 2 }.into());
 
-			//Check that the vote is in storage and the proposal updated properly
-			assert!(Voting::vote_casted(&1, &proposal_id));
-			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
-			assert_eq!(updated_proposal.ayes, vote_amount);
+			// The cost of the largest vote is held until the reveal.
+			assert_eq!(Balances::reserved_balance(&2), 49);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.voters), (0, 0));
 
-			//Vote nay and verify that the changes are correct in storage
-			Balances::make_free_balance_be(&2, 25u32.into());
-			assert_ok!(Voting::vote(
+			System::set_block_number(7);
+			assert_ok!(Voting::reveal_vote(
 				RuntimeOrigin::signed(2),
 				proposal_id,
-				VoteDecision::Nay(vote_amount)
+				VoteDecision::Aye(3),
+				SALT
 			));
-			System::assert_has_event(Event::VoteCasted { proposal_id, who: 2 }.into());
+			System::assert_last_event(
+				Event::VoteRevealed { proposal_id, who: 2, vote_decision: VoteDecision::Aye(3) }
+					.into(),
+			);
+			assert_eq!(Balances::reserved_balance(&2), 9);
+			assert!(Voting::commitment_of(&2, proposal_id).is_none());
 			assert!(Voting::vote_casted(&2, &proposal_id));
-			let updated_proposal: Proposal<Test> = Voting::get_proposal(&proposal_id).unwrap();
-			assert_eq!(updated_proposal.nays, vote_amount);
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.voters), (3, 1));
 		});
 	}
 
 	#[test]
-	fn voter_not_registered() {
+	fn suspended_voter_can_reveal() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(3)));
+			assert_ok!(Voting::suspend_voter(RuntimeOrigin::root(), 2));
 
-			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)),
-				Error::<Test>::VoterIsNotRegistered
-			);
+			System::set_block_number(7);
+			assert_ok!(Voting::reveal_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(3),
+				SALT
+			));
+			assert!(Voting::vote_casted(&2, &proposal_id));
+			assert_eq!(Balances::reserved_balance(&2), 9);
 		});
 	}
 
 	#[test]
-	fn vote_already_casted() {
+	fn phases_enforced() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			Balances::make_free_balance_be(&1, 25u32.into());
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
+			let proposal_id = before_each();
+			assert_noop!(submit_secret_proposal(4), Error::<Test>::TimePeriodToLow);
+			assert_noop!(
+				Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(3)),
+				Error::<Test>::SecretBallot
+			);
 
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(3)));
 			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)),
+				commit(2, proposal_id, &VoteDecision::Nay(3)),
 				Error::<Test>::VoteAlreadyCasted
 			);
+			assert_noop!(
+				Voting::reveal_vote(
+					RuntimeOrigin::signed(2),
+					proposal_id,
+					VoteDecision::Aye(3),
+					SALT
+				),
+				Error::<Test>::NotRevealPhase
+			);
+
+			System::set_block_number(7);
+			assert_noop!(
+				commit(3, proposal_id, &VoteDecision::Aye(3)),
+				Error::<Test>::CommitPhaseEnded
+			);
+			assert_noop!(
+				Voting::increase_proposal_time(RuntimeOrigin::signed(1), proposal_id, 20),
+				Error::<Test>::CommitPhaseEnded
+			);
+			assert_noop!(
+				Voting::reveal_vote(
+					RuntimeOrigin::signed(3),
+					proposal_id,
+					VoteDecision::Aye(3),
+					SALT
+				),
+				Error::<Test>::CommitmentNotFound
+			);
+
+			assert_ok!(submit_proposal(1, 20));
+			assert_noop!(
+				commit(2, proposal_id + 1, &VoteDecision::Aye(3)),
+				Error::<Test>::NotSecretBallot
+			);
 		});
 	}
 
 	#[test]
-	fn vote_over_limit() {
+	fn reveal_must_match() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			Balances::make_free_balance_be(&1, 25u32.into());
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(3)));
+			System::set_block_number(7);
 
-			let vote_limit: u32 = VoteLimit::get();
 			assert_noop!(
-				Voting::vote(
-					RuntimeOrigin::signed(1),
+				Voting::reveal_vote(
+					RuntimeOrigin::signed(2),
 					proposal_id,
-					VoteDecision::Aye(vote_limit + 1)
+					VoteDecision::Nay(3),
+					SALT
 				),
-				Error::<Test>::VoteAmountLimit
+				Error::<Test>::InvalidReveal
+			);
+			assert_noop!(
+				Voting::reveal_vote(
+					RuntimeOrigin::signed(2),
+					proposal_id,
+					VoteDecision::Aye(3),
+					[0; 32]
+				),
+				Error::<Test>::InvalidReveal
+			);
+
+			assert_ok!(Voting::reveal_vote(
+				RuntimeOrigin::signed(2),
+				proposal_id,
+				VoteDecision::Aye(3),
+				SALT
+			));
+			assert_noop!(
+				Voting::update_vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(4)),
+				Error::<Test>::SecretBallot
+			);
+			assert_noop!(
+				Voting::cancel_vote(RuntimeOrigin::signed(2), proposal_id),
+				Error::<Test>::SecretBallot
 			);
 		});
 	}
 
 	#[test]
-	fn invalid_proposal() {
+	fn unrevealed_commitments_ignored() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			Balances::make_free_balance_be(&1, 25u32.into());
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(5)));
+			assert_ok!(commit(3, proposal_id, &VoteDecision::Nay(2)));
+			System::set_block_number(7);
+			assert_ok!(Voting::reveal_vote(
+				RuntimeOrigin::signed(3),
+				proposal_id,
+				VoteDecision::Nay(2),
+				SALT
+			));
 
-			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(2)),
-				Error::<Test>::ProposalNotFound
+			System::set_block_number(11);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			let proposal = Voting::get_proposal(&proposal_id).unwrap();
+			assert_eq!((proposal.ayes, proposal.nays, proposal.voters), (0, 2, 1));
+			assert_eq!(proposal.status, ProposalStatus::Rejected);
+
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+			System::assert_last_event(
+				Event::CommitmentRemoved { proposal_id, who: 2, slashed: false }.into(),
 			);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(Balances::free_balance(&2), 100);
 		});
 	}
 
 	#[test]
-	fn ended_proposal() {
+	fn unrevealed_commitments_slashed() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			Balances::make_free_balance_be(&1, 25u32.into());
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 10));
+			SlashUnrevealedVotes::set(true);
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(5)));
 
-			System::set_block_number(20);
-
-			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(2)),
-				Error::<Test>::ProposalAlreadyEnded
+			System::set_block_number(11);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
+			assert_ok!(Voting::unlock_balance(RuntimeOrigin::signed(2), proposal_id));
+			System::assert_last_event(
+				Event::CommitmentRemoved { proposal_id, who: 2, slashed: true }.into(),
 			);
+			assert_eq!(Balances::reserved_balance(&2), 0);
+			assert_eq!(Balances::free_balance(&2), 51);
 		});
 	}
 
 	#[test]
-	fn invalid_vote_amount() {
+	fn removed_voter_refunded() {
 		new_test_ext().execute_with(|| {
-			//Initial setup
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 90));
-
-			assert_noop!(
-				Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(0)),
-				Error::<Test>::InvalidVoteAmount
-			);
+			let proposal_id = before_each();
+			assert_ok!(commit(2, proposal_id, &VoteDecision::Aye(5)));
+			assert_ok!(Voting::remove_voter(RuntimeOrigin::root(), 2));
+			assert!(Voting::commitment_of(&2, proposal_id).is_none());
+			assert_eq!(Balances::reserved_balance(&2), 0);
 		});
 	}
 }
 
-mod finish_proposal {
+mod enactment {
 	use super::*;
+	use codec::Encode;
+	use frame_support::traits::{Bounded, QueryPreimage, StorePreimage};
+
+	fn set_balance(who: u64, amount: u128) -> RuntimeCall {
+		RuntimeCall::Balances(pallet_balances::Call::set_balance {
+			who,
+			new_free: amount,
+			new_reserved: 0,
+		})
+	}
+
+	fn before_each(call: Bounded<RuntimeCall>) -> u32 {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&2, 25u32.into());
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 2));
+		let proposal_id = Voting::get_proposal_counter() + 1;
+		assert_ok!(Voting::make_proposal(
+			RuntimeOrigin::signed(1),
+			sp_core::H256::zero(),
+			params(),
+			5,
+			Some(call),
+		));
+		proposal_id
+	}
 
 	#[test]
-	fn proposal_passed() {
+	fn passed_proposal_dispatches_call() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			Balances::make_free_balance_be(&1, 25u32.into());
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
-
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(1)));
+			let call = set_balance(5, 100);
+			assert_ok!(Preimage::note_preimage(RuntimeOrigin::signed(1), call.encode()));
+			let hash = sp_core::blake2_256(&call.encode()).into();
+			let proposal_id = before_each(Preimage::pick(hash, call.encoded_size() as u32));
+			assert!(Preimage::is_requested(&hash));
 
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
 			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
 
-			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
-			System::assert_has_event(
-				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
-			);
+			System::assert_has_event(Event::ProposalEnacted { proposal_id, result: Ok(()) }.into());
+			assert_eq!(Balances::free_balance(&5), 100);
+			assert!(!Preimage::is_requested(&hash));
 		});
 	}
 
 	#[test]
-	fn proposal_rejected() {
+	fn failed_call_is_reported() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			Balances::make_free_balance_be(&1, 25u32.into());
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
-
-			assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Nay(1)));
+			//Transfers cannot be dispatched by root
+			let call = RuntimeCall::Balances(pallet_balances::Call::transfer { dest: 5, value: 1 });
+			let proposal_id = before_each(Preimage::bound(call).unwrap());
 
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
 			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
 
-			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
 			System::assert_has_event(
-				Event::ProposalEnded { proposal_id, status: ProposalStatus::Rejected }.into(),
+				Event::ProposalEnded { proposal_id, status: ProposalStatus::Passed }.into(),
+			);
+			System::assert_has_event(
+				Event::ProposalEnacted {
+					proposal_id,
+					result: Err(sp_runtime::DispatchError::BadOrigin),
+				}
+				.into(),
 			);
 		});
 	}
 
 	#[test]
-	fn proposal_tied() {
+	fn call_over_max_weight_is_not_dispatched() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(1);
-
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
+			let proposal_id = before_each(Preimage::bound(set_balance(5, 100)).unwrap());
+			MaxEnactmentWeight::set(frame_support::weights::Weight::zero());
 
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
 			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
 
-			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id));
 			System::assert_has_event(
-				Event::ProposalEnded { proposal_id, status: ProposalStatus::Tied }.into(),
+				Event::ProposalEnacted {
+					proposal_id,
+					result: Err(Error::<Test>::EnactmentWeightTooHigh.into()),
+				}
+				.into(),
 			);
+			assert_eq!(Balances::free_balance(&5), 0);
 		});
 	}
 
 	#[test]
-	fn finish_proposal_fails_if_canceled() {
+	fn call_not_enactable_is_not_dispatched() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(1);
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
-			assert_ok!(Voting::cancel_proposal(RuntimeOrigin::signed(1), proposal_id));
+			let call = RuntimeCall::System(frame_system::Call::set_code { code: Vec::new() });
+			let proposal_id = before_each(Preimage::bound(call).unwrap());
 
-			assert_noop!(
-				Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id),
-				Error::<Test>::ProposalAlreadyEnded
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Aye(1)));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
+
+			System::assert_has_event(
+				Event::ProposalEnacted {
+					proposal_id,
+					result: Err(Error::<Test>::CallNotEnactable.into()),
+				}
+				.into(),
 			);
 		});
 	}
 
 	#[test]
-	fn finish_proposal_early_rejects() {
+	fn rejected_proposal_is_not_enacted() {
 		new_test_ext().execute_with(|| {
-			let proposal_id = Voting::get_proposal_counter() + 1;
-			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
+			let proposal_id = before_each(Preimage::bound(set_balance(5, 100)).unwrap());
 
-			assert_noop!(
-				Voting::finish_proposal(RuntimeOrigin::signed(1), proposal_id),
-				Error::<Test>::ProposalAlreadyEnded
-			);
+			assert_ok!(Voting::vote(RuntimeOrigin::signed(2), proposal_id, VoteDecision::Nay(1)));
+			System::set_block_number(6);
+			assert_ok!(Voting::finish_proposal(RuntimeOrigin::signed(2), proposal_id));
+
+			assert!(!System::events().iter().any(|record| matches!(
+				record.event,
+				RuntimeEvent::Voting(Event::ProposalEnacted { .. })
+			)));
+			assert_eq!(Balances::free_balance(&5), 0);
 		});
 	}
 }
@@ -454,7 +3132,7 @@ mod unlock_balance {
 		let initial_balance: u32 = 25;
 		Balances::make_free_balance_be(&1, initial_balance.into());
 		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-		assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
+		assert_ok!(submit_proposal(1, 5));
 
 		(initial_balance, proposal_id)
 	}
@@ -537,11 +3215,7 @@ mod cancel_vote {
 		Balances::make_free_balance_be(&1, initial_balance.into());
 		let proposal_id = Voting::get_proposal_counter() + 1;
 		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-		assert_ok!(Voting::make_proposal(
-			RuntimeOrigin::signed(1),
-			sp_core::H256::zero(),
-			time_limit
-		));
+		assert_ok!(submit_proposal(1, time_limit));
 
 		assert_ok!(Voting::vote(RuntimeOrigin::signed(1), proposal_id, VoteDecision::Aye(3)));
 
@@ -600,7 +3274,7 @@ mod cancel_vote {
 			Balances::make_free_balance_be(&1, 25u32.into());
 			let proposal_id = Voting::get_proposal_counter() + 1;
 			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-			assert_ok!(Voting::make_proposal(RuntimeOrigin::signed(1), sp_core::H256::zero(), 5));
+			assert_ok!(submit_proposal(1, 5));
 
 			assert_noop!(
 				Voting::cancel_vote(RuntimeOrigin::signed(1), proposal_id),
@@ -633,11 +3307,7 @@ mod update_vote {
 		Balances::make_free_balance_be(&1, initial_balance.into());
 		let proposal_id = Voting::get_proposal_counter() + 1;
 		assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
-		assert_ok!(Voting::make_proposal(
-			RuntimeOrigin::signed(1),
-			sp_core::H256::zero(),
-			proposal_end.into()
-		));
+		assert_ok!(submit_proposal(1, proposal_end.into()));
 
 		(initial_balance, proposal_id)
 	}
@@ -992,3 +3662,114 @@ mod update_vote {
 		});
 	}
 }
+
+mod migrations {
+	use super::*;
+	use crate::{
+		migrations::v1::{v0, MigrateToV1},
+		CollateralMode, LockedVotes, Proposals, ProposalsEndingAt, RegisteredVoters, Vote,
+		VoteLocks, Votes,
+	};
+	use frame_support::{
+		storage::unhashed,
+		traits::{OnRuntimeUpgrade, StorageVersion},
+	};
+
+	#[test]
+	fn migrates_v0_voters_proposals_and_votes() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			StorageVersion::new(0).put::<Voting>();
+			unhashed::put(&RegisteredVoters::<Test>::hashed_key_for(1), &());
+			unhashed::put(
+				&Proposals::<Test>::hashed_key_for(0),
+				&v0::Proposal {
+					id: 0,
+					proposer: 1_u64,
+					text: sp_core::H256::zero(),
+					time_period: 50_u64,
+					status: ProposalStatus::InProgress,
+					ayes: 3,
+					nays: 0,
+				},
+			);
+			unhashed::put(
+				&Votes::<Test>::hashed_key_for(1, 0),
+				&v0::Vote { vote_decision: v0::VoteDecision::Aye(3), locked: true },
+			);
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::get::<Voting>(), 1);
+			let registration = RegisteredVoters::<Test>::get(1).unwrap();
+			assert_eq!(registration.registrar, None);
+			assert_eq!(registration.registered_at, 1);
+			let proposal = Proposals::<Test>::get(0).unwrap();
+			assert_eq!(proposal.status, ProposalStatus::InProgress);
+			assert_eq!(proposal.ayes, 3);
+			assert_eq!(proposal.voters, 1);
+			assert_eq!(proposal.threshold, ApprovalThreshold::SimpleMajority);
+			assert_eq!(ProposalsEndingAt::<Test>::get(50).to_vec(), vec![0]);
+			assert_eq!(
+				Votes::<Test>::get(1, 0),
+				Some(Vote {
+					vote_decision: VoteDecision::Aye(3),
+					locked: true,
+					round: 0,
+					paid: 0,
+					delegated: false
+				})
+			);
+			assert_eq!(LockedVotes::<Test>::get(1).to_vec(), vec![0]);
+		});
+	}
+
+	// Stores a locked v0 aye vote, with its cost reserved the way the first release did.
+	fn put_v0_vote(who: u64, proposal_id: u32, points: u32) {
+		Balances::make_free_balance_be(&who, 100);
+		assert_ok!(Balances::reserve(&who, (points * points).into()));
+		unhashed::put(
+			&Votes::<Test>::hashed_key_for(who, proposal_id),
+			&v0::Vote { vote_decision: v0::VoteDecision::Aye(points), locked: true },
+		);
+	}
+
+	#[test]
+	fn moves_v0_vote_reserves_into_the_named_reserve() {
+		new_test_ext().execute_with(|| {
+			put_v0_vote(1, 0, 3);
+			CostPerPointSquared::set(2);
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Voting::held_balance(&1), 18);
+			assert_eq!(Balances::reserved_balance(&1), 18);
+		});
+	}
+
+	#[test]
+	fn backs_v0_votes_with_a_lock() {
+		new_test_ext().execute_with(|| {
+			VoteCollateral::set(CollateralMode::Lock);
+			put_v0_vote(1, 0, 3);
+			put_v0_vote(1, 1, 2);
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(Balances::reserved_balance(&1), 0);
+			assert_eq!(VoteLocks::<Test>::get(1), 9);
+		});
+	}
+
+	#[test]
+	fn does_not_run_twice() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(1).put::<Voting>();
+			assert_ok!(Voting::register_voter(RuntimeOrigin::root(), 1));
+
+			MigrateToV1::<Test>::on_runtime_upgrade();
+
+			assert!(RegisteredVoters::<Test>::get(1).is_some());
+		});
+	}
+}
@@ -0,0 +1,92 @@
+#[cfg(feature = "runtime-benchmarks")]
+use frame_support::{
+	sp_io,
+	sp_runtime::{MultiSignature, MultiSigner},
+};
+use frame_support::{
+	sp_runtime::traits::{AtLeast32BitUnsigned, CheckedMul},
+	traits::Get,
+};
+use sp_std::marker::PhantomData;
+
+///Grants voting rights to accounts that are not in the list of registered voters.
+///
+///Lets the runtime plug in an external source of sybil resistance, such as identity judgements,
+/// instead of relying only on the registrar. It is checked every time a voter interacts with the
+/// pallet, so implementations should be cheap.
+pub trait VoterEligibility<AccountId> {
+	///Returns true if the account is allowed to vote without being registered.
+	fn is_eligible(who: &AccountId) -> bool;
+}
+
+///No account is eligible without being registered.
+impl<AccountId> VoterEligibility<AccountId> for () {
+	fn is_eligible(_who: &AccountId) -> bool {
+		false
+	}
+}
+
+///Cost of the points a voter gives to a single choice, in units of 'Balance'. The cost of a
+/// vote is the sum of the cost of each of its choices.
+///
+///The cost must not decrease when the amount of points grows, as lowering the cost of a vote is
+/// what the removal threshold guards against.
+pub trait VoteCost<Balance> {
+	///Returns the cost of 'points', or None if it overflows.
+	fn cost(points: u32) -> Option<Balance>;
+}
+
+///The classic quadratic voting cost: `points^2`.
+pub struct Quadratic;
+
+impl<Balance: AtLeast32BitUnsigned> VoteCost<Balance> for Quadratic {
+	fn cost(points: u32) -> Option<Balance> {
+		let points = Balance::from(points);
+		points.checked_mul(&points)
+	}
+}
+
+///Every point costs one: `points`.
+pub struct Linear;
+
+impl<Balance: AtLeast32BitUnsigned> VoteCost<Balance> for Linear {
+	fn cost(points: u32) -> Option<Balance> {
+		Some(Balance::from(points))
+	}
+}
+
+///Quadratic cost in multiples of 'Unit': `Unit * points^2`.
+pub struct ScaledQuadratic<Unit>(PhantomData<Unit>);
+
+impl<Balance: AtLeast32BitUnsigned, Unit: Get<u32>> VoteCost<Balance> for ScaledQuadratic<Unit> {
+	fn cost(points: u32) -> Option<Balance> {
+		<Quadratic as VoteCost<Balance>>::cost(points)?.checked_mul(&Balance::from(Unit::get()))
+	}
+}
+
+///Creates the signers and signatures of the votes submitted in the `submit_signed_votes`
+/// benchmark.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Signer, Signature> {
+	///Returns a new signer. 'index' tells apart the signers of a single benchmark.
+	fn signer(index: u32) -> Signer;
+	///Signs 'message' with the key of 'signer'.
+	fn sign(signer: &Signer, message: &[u8]) -> Signature;
+}
+
+///Signs with sr25519 keys generated in the keystore.
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<MultiSigner, MultiSignature> for () {
+	fn signer(_index: u32) -> MultiSigner {
+		sp_io::crypto::sr25519_generate(0.into(), None).into()
+	}
+
+	fn sign(signer: &MultiSigner, message: &[u8]) -> MultiSignature {
+		match signer {
+			MultiSigner::Sr25519(public) => sp_io::crypto::sr25519_sign(0.into(), public, message)
+				.expect("the key was generated in the keystore; qed")
+				.into(),
+			_ => unreachable!("only sr25519 signers are generated; qed"),
+		}
+	}
+}
@@ -1,7 +1,32 @@
 use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	sp_runtime::{
+		traits::{AtLeast32BitUnsigned, CheckedAdd, Saturating, Zero},
+		Perbill,
+	},
+	traits::Get,
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
-use crate::{Config, ProposalId};
+use crate::{BalanceOf, BoundedCallOf, Config, ProposalId, ProposalParamsOf, VoteCost};
+
+///Metadata and voting rules chosen when a proposal is made. `make_proposal` takes them
+/// unbounded and bounds them to the pallet limits.
+#[derive(Encode, Decode, TypeInfo, Debug, Clone, Eq, PartialEq, Default)]
+pub struct ProposalParams<Title = Vec<u8>, Summary = Vec<u8>, Uri = Vec<u8>, Options = Vec<Vec<u8>>>
+{
+	pub title: Title,
+	pub summary: Summary,
+	pub uri: Uri,
+	///Names of the options voters choose between. Empty for aye/nay proposals.
+	pub options: Options,
+	pub threshold: ApprovalThreshold,
+	///Whether votes are committed and revealed during the last `RevealPeriod` blocks, instead
+	/// of being cast in the open.
+	pub secret: bool,
+}
 
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone)]
 #[scale_info(skip_type_params(T))]
@@ -9,10 +34,27 @@ pub struct Proposal<T: Config> {
 	pub id: ProposalId,
 	pub proposer: T::AccountId,
 	pub text: T::Hash,
+	pub title: BoundedVec<u8, T::MaxTitleLength>,
+	pub summary: BoundedVec<u8, T::MaxSummaryLength>,
+	pub uri: BoundedVec<u8, T::MaxUriLength>,
+	///Names of the options voters choose between. Empty for aye/nay proposals.
+	pub options: BoundedVec<BoundedVec<u8, T::MaxOptionLength>, T::MaxOptions>,
 	pub time_period: T::BlockNumber,
+	pub threshold: ApprovalThreshold,
+	///Whether votes are committed and revealed during the last `RevealPeriod` blocks, instead
+	/// of being cast in the open.
+	pub secret: bool,
+	pub call: Option<BoundedCallOf<T>>,
 	pub status: ProposalStatus,
 	pub ayes: u32,
 	pub nays: u32,
+	///Points of the voters that took part without supporting either side. They count toward
+	/// quorum and turnout, but not toward the result.
+	pub abstains: u32,
+	///Points given to each option, in the order of `options`.
+	pub tallies: BoundedVec<u32, T::MaxOptions>,
+	pub voters: u32,
+	pub deposit: BalanceOf<T>,
 }
 
 impl<T: Config> Proposal<T> {
@@ -20,30 +62,253 @@ impl<T: Config> Proposal<T> {
 		id: ProposalId,
 		proposer: T::AccountId,
 		text: T::Hash,
+		params: ProposalParamsOf<T>,
 		time_period: T::BlockNumber,
+		call: Option<BoundedCallOf<T>>,
+		deposit: BalanceOf<T>,
 	) -> Self {
+		let ProposalParams { title, summary, uri, options, threshold, secret } = params;
+		let tallies = BoundedVec::truncate_from(sp_std::vec![0; options.len()]);
 		Proposal {
 			id,
 			proposer,
 			text,
+			title,
+			summary,
+			uri,
+			options,
 			time_period,
+			threshold,
+			secret,
+			call,
 			status: ProposalStatus::InProgress,
 			ayes: 0,
 			nays: 0,
+			abstains: 0,
+			tallies,
+			voters: 0,
+			deposit,
+		}
+	}
+
+	pub fn is_multi_option(&self) -> bool {
+		!self.options.is_empty()
+	}
+
+	///First block of the reveal window of a secret ballot, which ends with the proposal.
+	pub fn reveal_start(&self) -> T::BlockNumber {
+		self.time_period.saturating_sub(T::RevealPeriod::get())
+	}
+
+	///The most expensive vote the proposal accepts, whose cost commitments to secret ballots
+	/// hold until they are revealed.
+	pub fn max_decision(&self) -> VoteDecision<T::MaxOptions> {
+		let limit = T::VoteLimit::get();
+		if self.is_multi_option() {
+			VoteDecision::Options(BoundedVec::truncate_from(
+				sp_std::vec![limit; self.options.len()],
+			))
+		} else {
+			VoteDecision::Aye(limit)
+		}
+	}
+
+	///Checks that a vote decision has the shape of the proposal: aye, nay or split for aye/nay
+	/// proposals, and one amount of points per option for multi-option proposals. Any proposal
+	/// accepts abstentions.
+	pub fn accepts(&self, decision: &VoteDecision<T::MaxOptions>) -> bool {
+		match decision {
+			VoteDecision::Abstain(_) => true,
+			VoteDecision::Aye(_) | VoteDecision::Nay(_) | VoteDecision::Split { .. } =>
+				!self.is_multi_option(),
+			VoteDecision::Options(points) => points.len() == self.options.len(),
+		}
+	}
+
+	///Adds the points of a vote to the tally. The vote must be accepted by the proposal.
+	pub fn add_vote(&mut self, decision: &VoteDecision<T::MaxOptions>) {
+		match decision {
+			VoteDecision::Aye(v) => self.ayes = self.ayes.saturating_add(*v),
+			VoteDecision::Nay(v) => self.nays = self.nays.saturating_add(*v),
+			VoteDecision::Abstain(v) => self.abstains = self.abstains.saturating_add(*v),
+			VoteDecision::Split { aye, nay } => {
+				self.ayes = self.ayes.saturating_add(*aye);
+				self.nays = self.nays.saturating_add(*nay);
+			},
+			VoteDecision::Options(points) =>
+				for (tally, v) in self.tallies.iter_mut().zip(points.iter()) {
+					*tally = tally.saturating_add(*v);
+				},
+		}
+	}
+
+	///Removes the points of a vote from the tally.
+	pub fn remove_vote(&mut self, decision: &VoteDecision<T::MaxOptions>) {
+		match decision {
+			VoteDecision::Aye(v) => self.ayes = self.ayes.saturating_sub(*v),
+			VoteDecision::Nay(v) => self.nays = self.nays.saturating_sub(*v),
+			VoteDecision::Abstain(v) => self.abstains = self.abstains.saturating_sub(*v),
+			VoteDecision::Split { aye, nay } => {
+				self.ayes = self.ayes.saturating_sub(*aye);
+				self.nays = self.nays.saturating_sub(*nay);
+			},
+			VoteDecision::Options(points) =>
+				for (tally, v) in self.tallies.iter_mut().zip(points.iter()) {
+					*tally = tally.saturating_sub(*v);
+				},
+		}
+	}
+
+	///Total amount of points cast on the proposal, abstentions included.
+	pub fn points(&self) -> u32 {
+		let sides = self.ayes.saturating_add(self.nays).saturating_add(self.abstains);
+		self.tallies.iter().fold(sides, |total, v| total.saturating_add(*v))
+	}
+
+	///Result of a multi-option proposal: the option with the most points wins, unless several
+	/// options share the highest tally.
+	pub fn winning_option(&self) -> ProposalStatus {
+		let highest = self.tallies.iter().copied().max().unwrap_or_default();
+		let mut leaders = self.tallies.iter().enumerate().filter(|(_, v)| **v == highest);
+		match (leaders.next(), leaders.next()) {
+			(Some((index, _)), None) => ProposalStatus::Won(index as u32),
+			_ => ProposalStatus::Tied,
+		}
+	}
+}
+
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone)]
+#[scale_info(skip_type_params(T))]
+pub struct Registration<T: Config> {
+	pub registrar: Option<T::AccountId>,
+	pub registered_at: T::BlockNumber,
+	pub expires_at: Option<T::BlockNumber>,
+}
+
+impl<T: Config> Registration<T> {
+	pub fn new(registrar: Option<T::AccountId>) -> Self {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Registration {
+			registrar,
+			registered_at: now,
+			expires_at: T::RegistrationPeriod::get().map(|period| now.saturating_add(period)),
+		}
+	}
+
+	pub fn is_expired(&self, now: T::BlockNumber) -> bool {
+		self.expires_at.map_or(false, |expires_at| now >= expires_at)
+	}
+
+	///Extends the registration by a new period, starting from the current expiry or from now
+	/// if it already expired.
+	pub fn renew(&mut self) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let start = self.expires_at.map_or(now, |expires_at| expires_at.max(now));
+		self.expires_at = T::RegistrationPeriod::get().map(|period| start.saturating_add(period));
+	}
+}
+
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone)]
+#[scale_info(skip_type_params(T))]
+pub struct RegistrationRequest<T: Config> {
+	pub who: T::AccountId,
+	pub deposit: BalanceOf<T>,
+	pub requested_at: T::BlockNumber,
+}
+
+impl<T: Config> RegistrationRequest<T> {
+	pub fn new(who: T::AccountId, deposit: BalanceOf<T>) -> Self {
+		RegistrationRequest {
+			who,
+			deposit,
+			requested_at: <frame_system::Pallet<T>>::block_number(),
 		}
 	}
 }
 
-#[derive(Encode, Debug, Decode, Clone, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
-pub struct Vote {
-	pub vote_decision: VoteDecision,
+#[derive(
+	Encode,
+	RuntimeDebugNoBound,
+	Decode,
+	CloneNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+	EqNoBound,
+	PartialEqNoBound,
+)]
+#[scale_info(skip_type_params(MaxOptions))]
+pub struct Vote<MaxOptions: Get<u32>> {
+	pub vote_decision: VoteDecision<MaxOptions>,
 	pub locked: bool,
+	///Credit round whose budget last paid for the vote. It moves to the current round when an
+	/// update charges more in a later round.
+	pub round: u32,
+	///Voice credits taken from the budget of `round` for the vote. Reductions and cancels
+	/// refund at most this much, so earlier budgets are never refunded into a newer one.
+	pub paid: u32,
+	///Whether the vote was cast by the delegate of the voter rather than by the voter.
+	pub delegated: bool,
 }
 
-#[derive(Encode, Debug, Decode, Clone, TypeInfo, MaxEncodedLen, Eq, PartialEq)]
-pub enum VoteDecision {
+#[derive(
+	Encode,
+	RuntimeDebugNoBound,
+	Decode,
+	CloneNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+	EqNoBound,
+	PartialEqNoBound,
+)]
+#[scale_info(skip_type_params(MaxOptions))]
+pub enum VoteDecision<MaxOptions: Get<u32>> {
 	Aye(u32),
 	Nay(u32),
+	///Takes part in the proposal without supporting either side. The points count toward
+	/// quorum and turnout, and are paid for like any other vote.
+	Abstain(u32),
+	///Points divided between both sides of an aye/nay proposal. The cost is that of all the
+	/// points together, so splitting a vote is never cheaper than casting it on one side.
+	Split {
+		aye: u32,
+		nay: u32,
+	},
+	///Points given to each option of a multi-option proposal, in the order of its options.
+	Options(BoundedVec<u32, MaxOptions>),
+}
+
+impl<MaxOptions: Get<u32>> VoteDecision<MaxOptions> {
+	///Total amount of points of the vote.
+	pub fn points(&self) -> u32 {
+		match self {
+			VoteDecision::Aye(v) | VoteDecision::Nay(v) | VoteDecision::Abstain(v) => *v,
+			VoteDecision::Split { aye, nay } => aye.saturating_add(*nay),
+			VoteDecision::Options(points) =>
+				points.iter().fold(0u32, |total, v| total.saturating_add(*v)),
+		}
+	}
+
+	///Highest amount of points given to a single choice, which is limited by VoteLimit. Both
+	/// sides of a split vote make up a single choice.
+	pub fn max_points(&self) -> u32 {
+		match self {
+			VoteDecision::Aye(v) | VoteDecision::Nay(v) | VoteDecision::Abstain(v) => *v,
+			VoteDecision::Split { aye, nay } => aye.saturating_add(*nay),
+			VoteDecision::Options(points) => points.iter().copied().max().unwrap_or_default(),
+		}
+	}
+
+	///Cost of the vote: the sum of the cost of the points given to each choice. Returns None
+	/// on overflow.
+	pub fn cost<Balance: AtLeast32BitUnsigned, C: VoteCost<Balance>>(&self) -> Option<Balance> {
+		match self {
+			VoteDecision::Aye(v) | VoteDecision::Nay(v) | VoteDecision::Abstain(v) => C::cost(*v),
+			VoteDecision::Split { aye, nay } => C::cost(aye.checked_add(*nay)?),
+			VoteDecision::Options(points) => points
+				.iter()
+				.try_fold(Balance::zero(), |total, v| total.checked_add(&C::cost(*v)?)),
+		}
+	}
 }
 
 #[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Eq, PartialEq)]
@@ -54,4 +319,133 @@ pub enum ProposalStatus {
 	Passed,
 	Rejected,
 	Tied,
+	QuorumNotMet,
+	///The option at the given index of a multi-option proposal got the most points.
+	Won(u32),
+}
+
+///What voters put up to cast a vote.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, Eq, PartialEq)]
+pub enum CollateralMode {
+	///The cost of the vote is reserved from the voter's balance until the proposal ends.
+	Reserve,
+	///The cost of the vote is paid with voice credits. Every voter gets the same budget per
+	/// round, so the voting power does not depend on the voter's balance.
+	Credits,
+	///The voter's balance is locked for the largest cost among their votes that still hold
+	/// collateral, so the same balance backs votes on many proposals.
+	Lock,
+}
+
+///Hidden vote of a voter on a secret ballot, and the cost taken when committing to it.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Eq, PartialEq)]
+pub struct Commitment<Balance, Hash> {
+	pub hash: Hash,
+	pub cost: Balance,
+	///Credit round in which the commitment was made.
+	pub round: u32,
+}
+
+///Vote signed off-chain by a voter. The voter signs its encoding so a relayer can submit the
+/// vote with `submit_signed_votes`.
+#[derive(
+	Encode, RuntimeDebugNoBound, Decode, TypeInfo, CloneNoBound, EqNoBound, PartialEqNoBound,
+)]
+#[scale_info(skip_type_params(MaxOptions))]
+pub struct SignedVotePayload<Hash, MaxOptions: Get<u32>>
+where
+	Hash: Clone + Eq + core::fmt::Debug,
+{
+	///Hash of the genesis block of the chain, so the vote cannot be replayed on another chain.
+	pub genesis_hash: Hash,
+	pub proposal_id: ProposalId,
+	pub vote_decision: VoteDecision<MaxOptions>,
+	///The next signed-vote nonce of the voter, so the vote cannot be replayed.
+	pub nonce: u32,
+}
+
+///Proposals a delegation applies to.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, Eq, PartialEq)]
+pub enum DelegationScope {
+	///Every proposal.
+	All,
+	///A single proposal.
+	Proposal(ProposalId),
+}
+
+impl DelegationScope {
+	pub fn covers(&self, proposal_id: ProposalId) -> bool {
+		match self {
+			DelegationScope::All => true,
+			DelegationScope::Proposal(id) => *id == proposal_id,
+		}
+	}
+}
+
+///Voter whose votes an account follows, and on which proposals.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Eq, PartialEq)]
+pub struct Delegation<AccountId> {
+	pub delegate: AccountId,
+	pub scope: DelegationScope,
+}
+
+///Voice credits left to a voter in a credit round.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Eq, PartialEq)]
+pub struct CreditBalance {
+	pub round: u32,
+	pub credits: u32,
+}
+
+///Share of the vote points a proposal needs to pass, chosen when it is made.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ApprovalThreshold {
+	///More ayes than nays.
+	#[default]
+	SimpleMajority,
+	///More ayes than nays, and ayes make up at least the given share of all the points cast.
+	SuperMajority(Perbill),
+	///No participating voter voted nay.
+	Unanimity,
+}
+
+impl ApprovalThreshold {
+	///Result of a proposal with the given tally, once its quorum is met.
+	pub fn tally(&self, ayes: u32, nays: u32) -> ProposalStatus {
+		if ayes == nays {
+			return ProposalStatus::Tied
+		}
+		let approved = match self {
+			ApprovalThreshold::SimpleMajority => ayes > nays,
+			ApprovalThreshold::SuperMajority(share) =>
+				ayes > nays && ayes >= share.mul_ceil(ayes.saturating_add(nays)),
+			ApprovalThreshold::Unanimity => nays == 0,
+		};
+		if approved {
+			ProposalStatus::Passed
+		} else {
+			ProposalStatus::Rejected
+		}
+	}
+}
+
+///Position of the on_idle sweeper, stored so the work resumes in the next block.
+#[derive(Encode, Debug, Decode, TypeInfo, MaxEncodedLen, Clone, Eq, PartialEq)]
+pub enum SweepCursor<AccountId> {
+	///Finalizing proposals that ended. Holds the next ProposalId to check.
+	Proposals(ProposalId),
+	///Unlocking votes of finished proposals. Holds the last voter whose locked votes were
+	/// checked, or None to start from the first one.
+	Votes(Option<AccountId>),
+	///Removing expired registrations. Holds the last registered voter that was checked, or
+	/// None to start from the first one.
+	Registrations(Option<AccountId>),
+	///Taking lapsed eligible voters out of the quorum base. Holds the last eligible voter that
+	/// was checked, or None to start from the first one.
+	EligibleVoters(Option<AccountId>),
+}
+
+impl<AccountId> Default for SweepCursor<AccountId> {
+	fn default() -> Self {
+		SweepCursor::Proposals(1)
+	}
 }
@@ -0,0 +1,646 @@
+//! Placeholder weights for pallet_voting
+//!
+//! THESE WEIGHTS ARE NOT BENCHMARKED. They are hand-written estimates following the storage
+//! accesses of each extrinsic, kept only so the pallet can be wired into a runtime. Replace this
+//! file with the output of the benchmark command below, run on reference hardware, before using
+//! the pallet on a live chain, and again whenever an extrinsic or its storage access changes.
+
+// Command to generate this file:
+// ./target/release/node-template
+// benchmark
+// pallet
+// --chain
+// dev
+// --pallet
+// pallet_voting
+// --extrinsic
+// *
+// --steps=50
+// --repeat=20
+// --execution=wasm
+// --wasm-execution=compiled
+// --output
+// pallets/voting/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_voting.
+pub trait WeightInfo {
+	fn register_voter() -> Weight;
+	fn remove_voter(v: u32, d: u32) -> Weight;
+	fn suspend_voter() -> Weight;
+	fn reinstate_voter() -> Weight;
+	fn renew_registration() -> Weight;
+	fn issue_credits() -> Weight;
+	fn request_registration() -> Weight;
+	fn approve_registration() -> Weight;
+	fn reject_registration() -> Weight;
+	fn make_proposal() -> Weight;
+	fn make_options_proposal() -> Weight;
+	fn increase_proposal_time() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn vote(d: u32) -> Weight;
+	fn update_vote(d: u32) -> Weight;
+	fn cancel_vote(d: u32) -> Weight;
+	fn finish_proposal() -> Weight;
+	fn close_proposals(p: u32) -> Weight;
+	fn on_idle(p: u32) -> Weight;
+	fn prune_registration(d: u32) -> Weight;
+	fn prune_eligible_voter() -> Weight;
+	fn unlock_balance() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn submit_signed_votes(n: u32) -> Weight;
+	fn commit_vote() -> Weight;
+	fn reveal_vote() -> Weight;
+}
+
+/// Placeholder weights for pallet_voting, to be replaced by benchmarked weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn register_voter() -> Weight {
+		Weight::from_ref_time(18_412_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:1)
+	// Storage: Voting SuspendedVoters (r:0 w:1)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn remove_voter(v: u32, d: u32) -> Weight {
+		Weight::from_ref_time(24_310_000)
+			.saturating_add(Weight::from_ref_time(29_845_000).saturating_mul(v.into()))
+			.saturating_add(Weight::from_ref_time(3_412_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:1)
+	fn suspend_voter() -> Weight {
+		Weight::from_ref_time(17_224_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting SuspendedVoters (r:1 w:1)
+	fn reinstate_voter() -> Weight {
+		Weight::from_ref_time(16_538_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	fn renew_registration() -> Weight {
+		Weight::from_ref_time(16_920_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	fn issue_credits() -> Weight {
+		Weight::from_ref_time(17_641_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn request_registration() -> Weight {
+		Weight::from_ref_time(31_208_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn approve_registration() -> Weight {
+		Weight::from_ref_time(36_774_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_registration() -> Weight {
+		Weight::from_ref_time(33_915_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting ProposalCounter (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn make_proposal() -> Weight {
+		Weight::from_ref_time(44_750_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting ProposalCounter (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn make_options_proposal() -> Weight {
+		Weight::from_ref_time(47_120_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:2 w:2)
+	fn increase_proposal_time() -> Weight {
+		Weight::from_ref_time(29_048_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(38_230_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	fn vote(d: u32) -> Weight {
+		Weight::from_ref_time(52_417_000)
+			.saturating_add(Weight::from_ref_time(31_204_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	fn update_vote(d: u32) -> Weight {
+		Weight::from_ref_time(36_092_000)
+			.saturating_add(Weight::from_ref_time(58_117_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	fn cancel_vote(d: u32) -> Weight {
+		Weight::from_ref_time(35_180_000)
+			.saturating_add(Weight::from_ref_time(30_540_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	fn finish_proposal() -> Weight {
+		Weight::from_ref_time(44_380_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	fn close_proposals(p: u32) -> Weight {
+		Weight::from_ref_time(3_145_000)
+			.saturating_add(Weight::from_ref_time(41_388_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(p.into())))
+	}
+	// Storage: Voting SweptProposals (r:1 w:1)
+	// Storage: Voting NextSweep (r:1 w:1)
+	// Storage: Voting ProposalCounter (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting LockedVotes (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	fn on_idle(p: u32) -> Weight {
+		Weight::from_ref_time(7_012_000)
+			.saturating_add(Weight::from_ref_time(4_113_000).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:2 w:2)
+	// Storage: Voting RegisteredVoters (r:0 w:1)
+	// Storage: Voting SuspendedVoters (r:0 w:1)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn prune_registration(d: u32) -> Weight {
+		Weight::from_ref_time(19_240_000)
+			.saturating_add(Weight::from_ref_time(3_398_000).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting AmountEligibleVoters (r:1 w:1)
+	fn prune_eligible_voter() -> Weight {
+		Weight::from_ref_time(14_108_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock_balance() -> Weight {
+		Weight::from_ref_time(33_052_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:2 w:0)
+	// Storage: Voting SuspendedVoters (r:2 w:0)
+	// Storage: Voting Delegations (r:4 w:1)
+	// Storage: Voting Delegators (r:1 w:2)
+	fn delegate() -> Weight {
+		Weight::from_ref_time(29_871_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:1)
+	fn undelegate() -> Weight {
+		Weight::from_ref_time(18_964_000)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: Voting SignedVoteNonces (r:1 w:1)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	fn submit_signed_votes(n: u32) -> Weight {
+		Weight::from_ref_time(14_208_000)
+			.saturating_add(Weight::from_ref_time(71_936_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(0))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(0))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(38_117_000)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Votes (r:0 w:1)
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(46_503_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn register_voter() -> Weight {
+		Weight::from_ref_time(18_412_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:1)
+	// Storage: Voting SuspendedVoters (r:0 w:1)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn remove_voter(v: u32, d: u32) -> Weight {
+		Weight::from_ref_time(24_310_000)
+			.saturating_add(Weight::from_ref_time(29_845_000).saturating_mul(v.into()))
+			.saturating_add(Weight::from_ref_time(3_412_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:1)
+	fn suspend_voter() -> Weight {
+		Weight::from_ref_time(17_224_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Voting SuspendedVoters (r:1 w:1)
+	fn reinstate_voter() -> Weight {
+		Weight::from_ref_time(16_538_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	fn renew_registration() -> Weight {
+		Weight::from_ref_time(16_920_000)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	fn issue_credits() -> Weight {
+		Weight::from_ref_time(17_641_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn request_registration() -> Weight {
+		Weight::from_ref_time(31_208_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting RegisteredVoters (r:1 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn approve_registration() -> Weight {
+		Weight::from_ref_time(36_774_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Storage: Voting PendingRegistrations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reject_registration() -> Weight {
+		Weight::from_ref_time(33_915_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting ProposalCounter (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn make_proposal() -> Weight {
+		Weight::from_ref_time(44_750_000)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting ProposalCounter (r:1 w:1)
+	// Storage: Voting Proposals (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn make_options_proposal() -> Weight {
+		Weight::from_ref_time(47_120_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:2 w:2)
+	fn increase_proposal_time() -> Weight {
+		Weight::from_ref_time(29_048_000)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	fn cancel_proposal() -> Weight {
+		Weight::from_ref_time(38_230_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	fn vote(d: u32) -> Weight {
+		Weight::from_ref_time(52_417_000)
+			.saturating_add(Weight::from_ref_time(31_204_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(5))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	// Storage: Voting Delegations (r:1 w:0)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	fn update_vote(d: u32) -> Weight {
+		Weight::from_ref_time(36_092_000)
+			.saturating_add(Weight::from_ref_time(58_117_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(3))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	fn cancel_vote(d: u32) -> Weight {
+		Weight::from_ref_time(35_180_000)
+			.saturating_add(Weight::from_ref_time(30_540_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(4))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	fn finish_proposal() -> Weight {
+		Weight::from_ref_time(44_380_000)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting ProposalsEndingAt (r:1 w:1)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Preimage StatusFor (r:1 w:1)
+	// Storage: Preimage PreimageFor (r:1 w:0)
+	fn close_proposals(p: u32) -> Weight {
+		Weight::from_ref_time(3_145_000)
+			.saturating_add(Weight::from_ref_time(41_388_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(p.into())))
+	}
+	// Storage: Voting SweptProposals (r:1 w:1)
+	// Storage: Voting NextSweep (r:1 w:1)
+	// Storage: Voting ProposalCounter (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting LockedVotes (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	fn on_idle(p: u32) -> Weight {
+		Weight::from_ref_time(7_012_000)
+			.saturating_add(Weight::from_ref_time(4_113_000).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:2 w:2)
+	// Storage: Voting RegisteredVoters (r:0 w:1)
+	// Storage: Voting SuspendedVoters (r:0 w:1)
+	// Storage: Voting VoiceCredits (r:0 w:1)
+	// Storage: Voting AmountVoters (r:1 w:1)
+	fn prune_registration(d: u32) -> Weight {
+		Weight::from_ref_time(19_240_000)
+			.saturating_add(Weight::from_ref_time(3_398_000).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:0)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting AmountEligibleVoters (r:1 w:1)
+	fn prune_eligible_voter() -> Weight {
+		Weight::from_ref_time(14_108_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock_balance() -> Weight {
+		Weight::from_ref_time(33_052_000)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting RegisteredVoters (r:2 w:0)
+	// Storage: Voting SuspendedVoters (r:2 w:0)
+	// Storage: Voting Delegations (r:4 w:1)
+	// Storage: Voting Delegators (r:1 w:2)
+	fn delegate() -> Weight {
+		Weight::from_ref_time(29_871_000)
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// Storage: Voting Delegations (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:1)
+	fn undelegate() -> Weight {
+		Weight::from_ref_time(18_964_000)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: Voting SignedVoteNonces (r:1 w:1)
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting Delegators (r:1 w:0)
+	fn submit_signed_votes(n: u32) -> Weight {
+		Weight::from_ref_time(14_208_000)
+			.saturating_add(Weight::from_ref_time(71_936_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(0))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(0))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+	}
+	// Storage: Voting RegisteredVoters (r:1 w:0)
+	// Storage: Voting SuspendedVoters (r:1 w:0)
+	// Storage: Voting Proposals (r:1 w:0)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: Voting Votes (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting LockedVotes (r:1 w:1)
+	// Storage: Voting EligibleVoters (r:1 w:1)
+	fn commit_vote() -> Weight {
+		Weight::from_ref_time(38_117_000)
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Storage: Voting Proposals (r:1 w:1)
+	// Storage: Voting Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Voting Votes (r:0 w:1)
+	fn reveal_vote() -> Weight {
+		Weight::from_ref_time(46_503_000)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+}
@@ -28,6 +28,7 @@ use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
 use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_voting::CollateralMode;
 
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
@@ -131,6 +132,9 @@ pub const MINUTES: BlockNumber = 60_000 / (MILLISECS_PER_BLOCK as BlockNumber);
 pub const HOURS: BlockNumber = MINUTES * 60;
 pub const DAYS: BlockNumber = HOURS * 24;
 
+// One token, with 12 decimals.
+pub const UNIT: Balance = 1_000_000_000_000;
+
 /// The version information used to identify this runtime when compiled natively.
 #[cfg(feature = "std")]
 pub fn native_version() -> NativeVersion {
@@ -247,7 +251,7 @@ pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = ConstU32<50>;
-	type MaxReserves = ();
+	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -307,19 +311,179 @@ impl pallet_assets::Config for Runtime {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	pub const BasicDeposit: Balance = 10_000;
+	pub const FieldDeposit: Balance = 250;
+	pub const SubAccountDeposit: Balance = 2_000;
+}
+
+impl pallet_identity::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = ConstU32<100>;
+	type MaxAdditionalFields = ConstU32<100>;
+	type MaxRegistrars = ConstU32<20>;
+	type Slashed = ();
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const PreimageBaseDeposit: Balance = 1_000;
+	pub const PreimageByteDeposit: Balance = 1;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+}
+
+/// Identity registrars whose judgements are trusted to grant voting rights.
+pub const TRUSTED_IDENTITY_REGISTRARS: &[pallet_identity::RegistrarIndex] = &[0];
+
+/// Accounts with a `KnownGood` or `Reasonable` judgement from a trusted identity registrar
+/// can vote without being registered in pallet-voting.
+pub struct IdentityJudgement;
+
+impl pallet_voting::VoterEligibility<AccountId> for IdentityJudgement {
+	fn is_eligible(who: &AccountId) -> bool {
+		Identity::identity(who).map_or(false, |registration| {
+			registration.judgements.iter().any(|(registrar, judgement)| {
+				TRUSTED_IDENTITY_REGISTRARS.contains(registrar) &&
+					matches!(
+						judgement,
+						pallet_identity::Judgement::KnownGood |
+							pallet_identity::Judgement::Reasonable
+					)
+			})
+		})
+	}
+}
+
+/// Passed proposals dispatch their call as root, so they are limited to remarks and to managing
+/// the voting pallet itself. Anything else needs a more specific origin than root.
+pub struct VotingEnactableCalls;
+
+impl frame_support::traits::Contains<RuntimeCall> for VotingEnactableCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Voting(..)
+		)
+	}
+}
+
+/// Proposals can use any approval threshold, but super-majorities must require more than half
+/// of the points.
+pub struct VotingApprovalThresholds;
+
+impl frame_support::traits::Contains<pallet_voting::ApprovalThreshold>
+	for VotingApprovalThresholds
+{
+	fn contains(threshold: &pallet_voting::ApprovalThreshold) -> bool {
+		match threshold {
+			pallet_voting::ApprovalThreshold::SuperMajority(share) =>
+				*share > Perbill::from_percent(50),
+			_ => true,
+		}
+	}
+}
+
 parameter_types! {
 	pub const MaxVoters: u32 = 100;
+	pub const RegistrationPeriod: Option<BlockNumber> = Some(365 * DAYS);
 	pub const VoteRemovalThreshold: u32 = 20;
+	pub const RevealPeriod: BlockNumber = DAYS;
+	pub const SlashUnrevealedVotes: bool = true;
 	pub const VoteLimit: u32 = 5;
+	pub const MaxLockedVotes: u32 = 50;
+	pub const MaxDelegators: u32 = 16;
+	pub const MaxDelegationDepth: u32 = 4;
+	pub const MaxSignedVotes: u32 = 64;
+	pub const MaxProposalsEndingPerBlock: u32 = 10;
+	pub const MaxTitleLength: u32 = 128;
+	pub const MaxSummaryLength: u32 = 1024;
+	pub const MaxUriLength: u32 = 256;
+	pub const MaxOptions: u32 = 16;
+	pub const MaxOptionLength: u32 = 64;
+	pub const RegistrationDeposit: Balance = UNIT;
+	pub const MaxPendingRegistrations: u32 = 100;
+	pub const ProposalDeposit: Balance = 10 * UNIT;
+	pub const SlashCanceledProposals: bool = true;
+	pub const MinimumTurnout: u32 = 10;
+	pub const QuorumVoters: Perbill = Perbill::from_percent(10);
+	pub const QuorumPoints: u32 = 10;
+	/// One token per point squared.
+	pub const CostPerPointSquared: Balance = UNIT;
+	pub const VotingReserveId: [u8; 8] = *b"py/votng";
+	pub const VotingLockId: [u8; 8] = *b"py/votng";
+	pub const VoteCollateral: CollateralMode = CollateralMode::Reserve;
+	pub const CreditsPerRound: u32 = 100;
+	pub const CreditRoundLength: Option<BlockNumber> = Some(30 * DAYS);
+	pub EnactmentOrigin: RuntimeOrigin = frame_system::RawOrigin::Root.into();
+	/// Ten proposals ending in the same block can use at most half of it to enact their calls.
+	pub MaxEnactmentWeight: Weight = Perbill::from_percent(5) * BlockWeights::get().max_block;
 }
 
 /// Configure the pallet-voting in pallets/voting.
 impl pallet_voting::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type ReserveId = VotingReserveId;
+	type LockId = VotingLockId;
+	type RegistrarOrigin = EnsureRoot<AccountId>;
+	type RemovalOrigin = EnsureRoot<AccountId>;
+	type RegistrationDeposit = RegistrationDeposit;
+	type MaxPendingRegistrations = MaxPendingRegistrations;
+	type ProposalDeposit = ProposalDeposit;
+	type SlashCanceledProposals = SlashCanceledProposals;
+	type MinimumTurnout = MinimumTurnout;
+	type QuorumVoters = QuorumVoters;
+	type QuorumPoints = QuorumPoints;
+	type ApprovalThresholds = VotingApprovalThresholds;
+	type Slash = ();
+	type VoteCost = pallet_voting::Quadratic;
+	type CostPerPointSquared = CostPerPointSquared;
+	type CollateralMode = VoteCollateral;
+	type CreditsPerRound = CreditsPerRound;
+	type CreditRoundLength = CreditRoundLength;
+	type Eligibility = IdentityJudgement;
+	type RegistrationPeriod = RegistrationPeriod;
 	type MaxVoters = MaxVoters;
 	type VoteLimit = VoteLimit;
 	type VoteRemovalThreshold = VoteRemovalThreshold;
+	type RevealPeriod = RevealPeriod;
+	type SlashUnrevealedVotes = SlashUnrevealedVotes;
+	type MaxLockedVotes = MaxLockedVotes;
+	type MaxDelegators = MaxDelegators;
+	type MaxDelegationDepth = MaxDelegationDepth;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	type MaxSignedVotes = MaxSignedVotes;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type MaxProposalsEndingPerBlock = MaxProposalsEndingPerBlock;
+	type MaxTitleLength = MaxTitleLength;
+	type MaxSummaryLength = MaxSummaryLength;
+	type MaxUriLength = MaxUriLength;
+	type MaxOptions = MaxOptions;
+	type MaxOptionLength = MaxOptionLength;
+	type RuntimeCall = RuntimeCall;
+	type Preimages = Preimage;
+	type EnactmentOrigin = EnactmentOrigin;
+	type EnactableCalls = VotingEnactableCalls;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type WeightInfo = pallet_voting::weights::SubstrateWeight<Runtime>;
 }
 
 pub struct AuthorityToAccount;
@@ -363,6 +527,8 @@ construct_runtime!(
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
 		Sudo: pallet_sudo,
+		Identity: pallet_identity,
+		Preimage: pallet_preimage,
 		Assets: pallet_assets,
 		Dex: pallet_dex,
 		Dpos: pallet_dpos,
@@ -393,6 +559,9 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (pallet_voting::migrations::v1::MigrateToV1<Runtime>,);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -400,6 +569,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]